@@ -0,0 +1,1156 @@
+use crate::config::{CategoricalConfig, UnseenPolicy};
+use crate::edit::{self, Checked, Constraints, Violation};
+use crate::error::BinningError;
+use crate::evaluate::{self, Evaluation, Recount};
+use crate::json;
+use crate::numerical::Trend;
+use crate::precategorical::PreCatBinStats;
+use crate::significance;
+use crate::special::SpecialTally;
+use crate::stability::{self, Fitted, Stability};
+use crate::validate;
+use crate::weights::BinWeights;
+use crate::woeiv::{calc_event_rate, calc_woe_iv};
+use ndarray::Array2;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatBin {
+    pub bin_id: usize,
+    pub indices: Vec<i32>,
+    pub pos: i32,
+    pub neg: i32,
+    #[serde(with = "json::float")]
+    pub woe: f64,
+    #[serde(with = "json::float")]
+    pub iv: f64,
+    pub is_missing: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_special: bool,
+    /// P-value of the significance test against the previous regular bin,
+    /// set when the config has a `significance` limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pvalue: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weights: Option<BinWeights>,
+}
+
+impl CatBin {
+    #[inline]
+    pub fn count(&self) -> i32 {
+        self.pos + self.neg
+    }
+
+    #[inline]
+    pub fn pos_weight(&self) -> f64 {
+        self.weights.map_or(self.pos as f64, |w| w.pos)
+    }
+
+    #[inline]
+    pub fn neg_weight(&self) -> f64 {
+        self.weights.map_or(self.neg as f64, |w| w.neg)
+    }
+
+    #[inline]
+    pub fn total_weight(&self) -> f64 {
+        self.pos_weight() + self.neg_weight()
+    }
+
+    #[inline]
+    pub fn event_rate(&self) -> f64 {
+        let total = self.total_weight();
+        if total > 0.0 {
+            self.pos_weight() / total
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Recount for CatBin {
+    #[inline]
+    fn is_regular(&self) -> bool {
+        !self.is_missing && !self.is_special
+    }
+
+    #[inline]
+    fn woe(&self) -> f64 {
+        self.woe
+    }
+
+    #[inline]
+    fn iv(&self) -> f64 {
+        self.iv
+    }
+
+    #[inline]
+    fn counts(&self) -> (i32, i32) {
+        (self.pos, self.neg)
+    }
+
+    fn recount(&mut self, pos: i32, neg: i32, woe: f64, iv: f64) {
+        (self.pos, self.neg) = (pos, neg);
+        (self.woe, self.iv) = (woe, iv);
+        self.weights = None;
+    }
+
+    fn set_pvalue(&mut self, pvalue: f64) {
+        self.pvalue = Some(pvalue);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoricalModel {
+    pub config: CategoricalConfig,
+    pub bins: Vec<CatBin>,
+    /// WoE trend of the fitted regular bins, which edits must keep; `None`
+    /// when sample weights leave them out of order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trend: Option<Trend>,
+    /// Prebin statistics kept for editing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prebins: Option<PreCatBinStats>,
+}
+
+impl CategoricalModel {
+    pub fn transform(&self, x: &[i32]) -> Result<Vec<f64>, BinningError> {
+        CategoricalBinning::execute_transform(x, &self.bins, &self.config).map(|(woe, _)| woe)
+    }
+
+    /// Like `transform`, also returning how many values were unseen during fit.
+    pub fn transform_with_unseen(&self, x: &[i32]) -> Result<(Vec<f64>, usize), BinningError> {
+        CategoricalBinning::execute_transform(x, &self.bins, &self.config)
+    }
+
+    pub fn total_iv(&self) -> f64 {
+        self.bins.iter().map(|b| b.iv).sum()
+    }
+
+    /// Stability of `x` against the fitted bins, with the outcome drift per
+    /// bin when `y` is given.
+    pub fn stability(&self, x: &[i32], y: Option<&[i32]>) -> Result<Stability, BinningError> {
+        validate::check_new_sample(x.iter().map(|_| false))?;
+        if let Some(y) = y {
+            validate::check_new_target(x.len(), y.iter())?;
+        }
+        let fitted: Vec<Fitted> = self
+            .bins
+            .iter()
+            .map(|b| Fitted {
+                bin_id: b.bin_id,
+                woe: b.woe,
+                iv: b.iv,
+                weight: b.total_weight(),
+            })
+            .collect();
+        let positions = CategoricalBinning::bin_positions(x, &self.bins, self.config.missing_code);
+        let targets = (0..x.len()).map(|i| y.map(|y| y[i]));
+        Ok(stability::compare(
+            &fitted,
+            positions.into_iter().zip(targets),
+            y.is_some(),
+        ))
+    }
+
+    /// Recomputes the bin table on `x` and `y` without refitting, flagging
+    /// the bins whose WoE changed sign or order.
+    pub fn evaluate(&self, x: &[i32], y: &[i32]) -> Result<Evaluation<CatBin>, BinningError> {
+        let missing = self.config.missing_code;
+        validate::check_binary(x.iter().map(|v| (*v == missing, false)), y.iter())?;
+        let positions = CategoricalBinning::bin_positions(x, &self.bins, missing);
+        Ok(evaluate::evaluate(
+            &self.bins,
+            positions.into_iter().zip(y.iter().copied()),
+            self.config.significance,
+        ))
+    }
+
+    /// Merges regular bin `bin_id` with the one after it and returns the
+    /// constraints the edited bins break.
+    pub fn merge_bins(&mut self, bin_id: usize) -> Result<Vec<Violation>, BinningError> {
+        let mut groups = self.groups()?;
+        edit::check_mergeable(bin_id, groups.len())?;
+        let next = groups.remove(bin_id + 1);
+        groups[bin_id].extend(next);
+        self.rebuild(groups)
+    }
+
+    /// Moves `category` into regular bin `to_bin` and returns the constraints
+    /// the edited bins break.
+    pub fn move_category(
+        &mut self,
+        category: i32,
+        to_bin: usize,
+    ) -> Result<Vec<Violation>, BinningError> {
+        let mut groups = self.groups()?;
+        edit::check_regular(to_bin, groups.len())?;
+        let stats = self.prebins.as_ref().ok_or_else(edit::no_prebins)?;
+        let prebin = stats
+            .indices
+            .iter()
+            .position(|&c| c == category)
+            .ok_or_else(|| {
+                BinningError::InvalidInput(format!("Category {} is not in a regular bin", category))
+            })?;
+        let from_bin = groups
+            .iter()
+            .position(|g| g.contains(&prebin))
+            .ok_or_else(edit::no_prebins)?;
+        if from_bin != to_bin {
+            if groups[from_bin].len() == 1 {
+                return Err(BinningError::InvalidInput(format!(
+                    "Moving category {} would empty bin {}; merge the bins instead",
+                    category, from_bin
+                )));
+            }
+            groups[from_bin].retain(|&i| i != prebin);
+            groups[to_bin].push(prebin);
+        }
+        self.rebuild(groups)
+    }
+
+    /// Constraints the current regular bins break.
+    pub fn violations(&self) -> Vec<Violation> {
+        let regular: Vec<Checked> = self
+            .bins
+            .iter()
+            .filter(|b| !b.is_missing && !b.is_special)
+            .map(|b| Checked {
+                bin_id: b.bin_id,
+                woe: b.woe,
+                event_rate: b.event_rate(),
+                weight: b.total_weight(),
+                pos: b.pos,
+                neg: b.neg,
+            })
+            .collect();
+        Constraints {
+            max_bins: self.config.max_bins,
+            min_bin_pct: self.config.min_bin_pct,
+            max_bin_pct: self.config.max_bin_pct,
+            event_limits: self.config.event_limits,
+            significance: self.config.significance,
+            separation: self.config.separation,
+            trend: self.trend,
+        }
+        .check(&regular)
+    }
+
+    /// Prebin indices of the categories in each regular bin.
+    fn groups(&self) -> Result<Vec<Vec<usize>>, BinningError> {
+        let stats = self.prebins.as_ref().ok_or_else(edit::no_prebins)?;
+        let prebin_of: HashMap<i32, usize> = stats
+            .indices
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| (c, i))
+            .collect();
+        self.bins
+            .iter()
+            .filter(|b| !b.is_missing && !b.is_special)
+            .map(|b| {
+                b.indices
+                    .iter()
+                    .map(|c| prebin_of.get(c).copied().ok_or_else(edit::no_prebins))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn rebuild(&mut self, groups: Vec<Vec<usize>>) -> Result<Vec<Violation>, BinningError> {
+        let stats = self.prebins.as_ref().ok_or_else(edit::no_prebins)?;
+        self.bins = CategoricalBinning::new(self.config.clone()).reconstruct_bins(stats, groups);
+        Ok(self.violations())
+    }
+
+    pub fn to_json(&self) -> Result<String, BinningError> {
+        json::to_json("categorical", self)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, BinningError> {
+        let model: Self = json::from_json("categorical", json)?;
+        model.config.validate()?;
+        Ok(model)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CategoricalBinning {
+    pub config: CategoricalConfig,
+}
+
+impl CategoricalBinning {
+    pub fn new(config: CategoricalConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn fit(&self, x: &[i32], y: &[i32]) -> Result<CategoricalModel, BinningError> {
+        self.execute_fit(x, y, None)
+    }
+
+    pub fn fit_weighted(
+        &self,
+        x: &[i32],
+        y: &[i32],
+        sample_weight: &[f64],
+    ) -> Result<CategoricalModel, BinningError> {
+        self.execute_fit(x, y, Some(sample_weight))
+    }
+
+    pub fn execute_fit(
+        &self,
+        x: &[i32],
+        y: &[i32],
+        sample_weight: Option<&[f64]>,
+    ) -> Result<CategoricalModel, BinningError> {
+        let missing = self.config.missing_code;
+        let special = &self.config.special_codes;
+        validate::check_binary(
+            x.iter()
+                .map(|v| (*v == missing || special.contains(v), false)),
+            y.iter(),
+        )?;
+        if let Some(w) = sample_weight {
+            validate::check_weights(
+                w.iter(),
+                x.iter()
+                    .zip(y)
+                    .map(|(v, &t)| (*v == missing || special.contains(v), t)),
+            )?;
+        }
+
+        let stats = match sample_weight {
+            Some(w) => self.prebinning_weighted(x, y, w),
+            None => self.prebinning(x, y),
+        };
+        let mut splits = self.split(&stats);
+        if splits.is_none()
+            && let Some(significance) = self.config.significance
+        {
+            // No grouping separates every adjacent pair, so solve without the
+            // limit and merge the least significant pairs afterwards.
+            let relaxed = CategoricalBinning::new(CategoricalConfig {
+                significance: None,
+                ..self.config.clone()
+            });
+            let max_samples =
+                ((stats.total_wpos + stats.total_wneg) * self.config.max_bin_pct).floor();
+            splits = relaxed.split(&stats).map(|splits| {
+                significance::merge_insignificant(
+                    &significance,
+                    splits,
+                    stats.indices.len(),
+                    &[],
+                    |start, end| stats.get_counts(start, end),
+                    |start, end| {
+                        let (wpos, wneg) = stats.get_weights(start, end);
+                        let (pos, neg) = stats.get_counts(start, end);
+                        wpos + wneg <= max_samples && self.config.event_limits.allows(pos, neg)
+                    },
+                )
+            });
+        }
+        let splits = match splits {
+            Some(splits) => splits,
+            None if !self.config.event_limits.is_none() => {
+                return Err(self
+                    .config
+                    .event_limits
+                    .infeasible(stats.total_pos, stats.total_neg));
+            }
+            None => Vec::new(),
+        };
+        let mut groups = Vec::new();
+        let mut start = 0;
+        for end in splits.into_iter().chain([stats.indices.len() - 1]) {
+            groups.push((start..=end).collect());
+            start = end + 1;
+        }
+        let bins = self.reconstruct_bins(&stats, groups);
+        Ok(CategoricalModel {
+            config: self.config.clone(),
+            trend: Self::fitted_trend(&bins),
+            bins,
+            prebins: Some(stats),
+        })
+    }
+
+    fn prebinning(&self, x: &[i32], y: &[i32]) -> PreCatBinStats {
+        let missing = self.config.missing_code;
+        let (mut final_map, m_pos, m_neg) = x
+            .par_iter()
+            .zip(y.par_iter())
+            .fold(
+                || (HashMap::<i32, (i32, i32)>::new(), 0, 0),
+                |(mut map, mut mp, mut mn), (&val, &target)| {
+                    if val == missing {
+                        if target == 1 {
+                            mp += 1;
+                        } else {
+                            mn += 1;
+                        }
+                    } else {
+                        let entry = map.entry(val).or_insert((0, 0));
+                        if target == 1 {
+                            entry.0 += 1;
+                        } else {
+                            entry.1 += 1;
+                        }
+                    }
+                    (map, mp, mn)
+                },
+            )
+            .reduce(
+                || (HashMap::new(), 0, 0),
+                |(mut map1, mp1, mn1), (map2, mp2, mn2)| {
+                    for (k, v) in map2 {
+                        let e = map1.entry(k).or_insert((0, 0));
+                        e.0 += v.0;
+                        e.1 += v.1;
+                    }
+                    (map1, mp1 + mp2, mn1 + mn2)
+                },
+            );
+
+        let special_tally = self
+            .config
+            .special_codes
+            .iter()
+            .map(|code| {
+                let (p, n) = final_map.remove(code).unwrap_or((0, 0));
+                SpecialTally {
+                    pos: p,
+                    neg: n,
+                    weights: BinWeights {
+                        pos: p as f64,
+                        neg: n as f64,
+                    },
+                }
+            })
+            .collect();
+
+        let mut map_stats: Vec<(i32, i32, i32)> = final_map
+            .into_iter()
+            .map(|(id, (p, n))| (id, p, n))
+            .collect();
+
+        map_stats.sort_by(|a, b| {
+            let br_a = if a.1 + a.2 > 0 {
+                a.1 as f64 / (a.1 + a.2) as f64
+            } else {
+                0.0
+            };
+            let br_b = if b.1 + b.2 > 0 {
+                b.1 as f64 / (b.1 + b.2) as f64
+            } else {
+                0.0
+            };
+            br_a.partial_cmp(&br_b).unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let mut pos_counts = Vec::with_capacity(map_stats.len());
+        let mut neg_counts = Vec::with_capacity(map_stats.len());
+        let mut final_indices = Vec::with_capacity(map_stats.len());
+
+        for (id, p, n) in map_stats {
+            final_indices.push(id);
+            pos_counts.push(p);
+            neg_counts.push(n);
+        }
+
+        PreCatBinStats::new(&pos_counts, &neg_counts, final_indices, m_pos, m_neg)
+            .with_special(special_tally)
+            .with_divergence(self.config.divergence)
+    }
+
+    fn prebinning_weighted(&self, x: &[i32], y: &[i32], w: &[f64]) -> PreCatBinStats {
+        type Acc = (i32, i32, f64, f64);
+        let missing_code = self.config.missing_code;
+        let (mut final_map, missing) = x
+            .par_iter()
+            .zip(y.par_iter())
+            .zip(w.par_iter())
+            .fold(
+                || (HashMap::<i32, Acc>::new(), (0, 0, 0.0, 0.0)),
+                |(mut map, mut missing), ((&val, &target), &weight)| {
+                    let entry = if val == missing_code {
+                        &mut missing
+                    } else {
+                        map.entry(val).or_insert((0, 0, 0.0, 0.0))
+                    };
+                    if target == 1 {
+                        entry.0 += 1;
+                        entry.2 += weight;
+                    } else {
+                        entry.1 += 1;
+                        entry.3 += weight;
+                    }
+                    (map, missing)
+                },
+            )
+            .reduce(
+                || (HashMap::new(), (0, 0, 0.0, 0.0)),
+                |(mut map1, m1), (map2, m2)| {
+                    for (k, v) in map2 {
+                        let e = map1.entry(k).or_insert((0, 0, 0.0, 0.0));
+                        e.0 += v.0;
+                        e.1 += v.1;
+                        e.2 += v.2;
+                        e.3 += v.3;
+                    }
+                    (map1, (m1.0 + m2.0, m1.1 + m2.1, m1.2 + m2.2, m1.3 + m2.3))
+                },
+            );
+
+        let special_tally = self
+            .config
+            .special_codes
+            .iter()
+            .map(|code| {
+                let (p, n, wp, wn) = final_map.remove(code).unwrap_or((0, 0, 0.0, 0.0));
+                SpecialTally {
+                    pos: p,
+                    neg: n,
+                    weights: BinWeights { pos: wp, neg: wn },
+                }
+            })
+            .collect();
+
+        let mut map_stats: Vec<(i32, Acc)> = final_map.into_iter().collect();
+
+        map_stats.sort_by(|a, b| {
+            let br_a = if a.1.2 + a.1.3 > 0.0 {
+                a.1.2 / (a.1.2 + a.1.3)
+            } else {
+                0.0
+            };
+            let br_b = if b.1.2 + b.1.3 > 0.0 {
+                b.1.2 / (b.1.2 + b.1.3)
+            } else {
+                0.0
+            };
+            br_a.partial_cmp(&br_b).unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let mut pos_counts = Vec::with_capacity(map_stats.len());
+        let mut neg_counts = Vec::with_capacity(map_stats.len());
+        let mut pos_weights = Vec::with_capacity(map_stats.len());
+        let mut neg_weights = Vec::with_capacity(map_stats.len());
+        let mut final_indices = Vec::with_capacity(map_stats.len());
+
+        for (id, (p, n, wp, wn)) in map_stats {
+            final_indices.push(id);
+            pos_counts.push(p);
+            neg_counts.push(n);
+            pos_weights.push(wp);
+            neg_weights.push(wn);
+        }
+
+        let missing_weights = BinWeights {
+            pos: missing.2,
+            neg: missing.3,
+        };
+        PreCatBinStats::new(
+            &pos_counts,
+            &neg_counts,
+            final_indices,
+            missing.0,
+            missing.1,
+        )
+        .with_weights(&pos_weights, &neg_weights, missing_weights)
+        .with_special(special_tally)
+        .with_divergence(self.config.divergence)
+    }
+
+    /// `None` when no binning meets the bin size and event count limits.
+    fn split(&self, stats: &PreCatBinStats) -> Option<Vec<usize>> {
+        let n = stats.indices.len();
+        let k_max = self.config.max_bins.min(n);
+        let total_samples = stats.total_wpos + stats.total_wneg;
+        let min_samples = (total_samples * self.config.min_bin_pct).floor();
+        let max_samples = (total_samples * self.config.max_bin_pct).floor();
+        let event_limits = &self.config.event_limits;
+        let significance = self.config.significance;
+        let separation = self.config.separation;
+        let mut dp = Array2::<f64>::from_elem((k_max + 1, n), f64::NEG_INFINITY);
+        let mut best_split = Array2::<usize>::from_elem((k_max + 1, n), 0);
+
+        for i in 0..n {
+            let (p, n_c) = stats.get_weights(0, i);
+            let total = p + n_c;
+            let (pos, neg) = stats.get_counts(0, i);
+            if total >= min_samples && total <= max_samples && event_limits.allows(pos, neg) {
+                dp[[1, i]] = stats.calc_iv_range(0, i);
+            }
+        }
+
+        for k in 2..=k_max {
+            for i in (k - 1)..n {
+                for j in (k - 2)..i {
+                    if dp[[k - 1, j]] == f64::NEG_INFINITY {
+                        continue;
+                    }
+                    let (cur_p, cur_n) = stats.get_weights(j + 1, i);
+                    if cur_p + cur_n < min_samples || cur_p + cur_n > max_samples {
+                        continue;
+                    }
+                    let (pos, neg) = stats.get_counts(j + 1, i);
+                    if !event_limits.allows(pos, neg) {
+                        continue;
+                    }
+                    if significance.is_some() || !separation.is_none() {
+                        let prev_start = if k == 2 {
+                            0
+                        } else {
+                            best_split[[k - 1, j]] + 1
+                        };
+                        if let Some(significance) = significance
+                            && !significance.separates(stats.get_counts(prev_start, j), (pos, neg))
+                        {
+                            continue;
+                        }
+                        let (prev_p, prev_n) = stats.get_weights(prev_start, j);
+                        if !separation.separates(
+                            (
+                                stats.calc_woe_single(prev_p, prev_n),
+                                calc_event_rate(prev_p, prev_n),
+                            ),
+                            (
+                                stats.calc_woe_single(cur_p, cur_n),
+                                calc_event_rate(cur_p, cur_n),
+                            ),
+                        ) {
+                            continue;
+                        }
+                    }
+                    let current_iv = dp[[k - 1, j]] + stats.calc_iv_range(j + 1, i);
+                    if current_iv > dp[[k, i]] {
+                        dp[[k, i]] = current_iv;
+                        best_split[[k, i]] = j;
+                    }
+                }
+            }
+        }
+
+        let mut final_k = k_max;
+        while final_k > 1 && dp[[final_k, n - 1]] == f64::NEG_INFINITY {
+            final_k -= 1;
+        }
+        if dp[[final_k, n - 1]] == f64::NEG_INFINITY {
+            return None;
+        }
+
+        let mut splits = Vec::new();
+        let mut curr_i = n - 1;
+        let mut k_ptr = final_k;
+        while k_ptr > 1 {
+            let split_pt = best_split[[k_ptr, curr_i]];
+            splits.push(split_pt);
+            curr_i = split_pt;
+            k_ptr -= 1;
+        }
+        splits.sort();
+        Some(splits)
+    }
+
+    /// Builds the bins from the prebin indices in each regular bin.
+    fn reconstruct_bins(&self, stats: &PreCatBinStats, groups: Vec<Vec<usize>>) -> Vec<CatBin> {
+        let special_pos: f64 = stats.special.iter().map(|s| s.weights.pos).sum();
+        let special_neg: f64 = stats.special.iter().map(|s| s.weights.neg).sum();
+        let grand_total_pos = stats.total_wpos + stats.missing_weights.pos + special_pos;
+        let grand_total_neg = stats.total_wneg + stats.missing_weights.neg + special_neg;
+
+        let mut bins: Vec<CatBin> = Vec::new();
+        for (b_id, mut group) in groups.into_iter().enumerate() {
+            group.sort_unstable();
+            let (mut pos, mut neg) = (0, 0);
+            let (mut wpos, mut wneg) = (0.0, 0.0);
+            for run in group.chunk_by(|&a, &b| b == a + 1) {
+                let (start_idx, end_idx) = (run[0], run[run.len() - 1]);
+                let (p, n) = stats.get_counts(start_idx, end_idx);
+                let (wp, wn) = stats.get_weights(start_idx, end_idx);
+                pos += p;
+                neg += n;
+                wpos += wp;
+                wneg += wn;
+            }
+
+            let indices = group.iter().map(|&i| stats.indices[i]).collect();
+            let (woe, iv) = calc_woe_iv(wpos, wneg, grand_total_pos, grand_total_neg);
+            let pvalue = match (self.config.significance, bins.last()) {
+                (Some(significance), Some(prev)) => Some(significance::pvalue(
+                    significance.test,
+                    (prev.pos, prev.neg),
+                    (pos, neg),
+                )),
+                _ => None,
+            };
+            bins.push(CatBin {
+                bin_id: b_id,
+                indices,
+                pos,
+                neg,
+                woe,
+                iv,
+                is_missing: false,
+                is_special: false,
+                pvalue,
+                weights: stats.is_weighted.then_some(BinWeights {
+                    pos: wpos,
+                    neg: wneg,
+                }),
+            });
+        }
+
+        for (&code, tally) in self.config.special_codes.iter().zip(stats.special.iter()) {
+            let (woe, iv) = if tally.pos + tally.neg > 0 {
+                calc_woe_iv(
+                    tally.weights.pos,
+                    tally.weights.neg,
+                    grand_total_pos,
+                    grand_total_neg,
+                )
+            } else {
+                (0.0, 0.0)
+            };
+            bins.push(CatBin {
+                bin_id: bins.len(),
+                indices: vec![code],
+                pos: tally.pos,
+                neg: tally.neg,
+                woe,
+                iv,
+                is_missing: false,
+                is_special: true,
+                pvalue: None,
+                weights: stats.is_weighted.then_some(tally.weights),
+            });
+        }
+
+        if stats.missing_pos + stats.missing_neg > 0 {
+            let (woe, iv) = calc_woe_iv(
+                stats.missing_weights.pos,
+                stats.missing_weights.neg,
+                grand_total_pos,
+                grand_total_neg,
+            );
+            bins.push(CatBin {
+                bin_id: bins.len(),
+                indices: vec![self.config.missing_code],
+                pos: stats.missing_pos,
+                neg: stats.missing_neg,
+                woe,
+                iv,
+                is_missing: true,
+                is_special: false,
+                pvalue: None,
+                weights: stats.is_weighted.then_some(stats.missing_weights),
+            });
+        }
+        bins
+    }
+
+    /// Categories are grouped in event rate order, so the regular bins
+    /// ascend in WoE unless weights reorder them.
+    fn fitted_trend(bins: &[CatBin]) -> Option<Trend> {
+        let woes: Vec<f64> = bins
+            .iter()
+            .filter(|b| !b.is_missing && !b.is_special)
+            .map(|b| b.woe)
+            .collect();
+        if woes.windows(2).all(|w| w[0] <= w[1]) {
+            Some(Trend::Increasing)
+        } else if woes.windows(2).all(|w| w[0] >= w[1]) {
+            Some(Trend::Decreasing)
+        } else {
+            None
+        }
+    }
+
+    fn unseen_woe(bins: &[CatBin], config: &CategoricalConfig) -> Option<f64> {
+        let regular = bins.iter().filter(|b| !b.is_missing && !b.is_special);
+        match config.unseen {
+            UnseenPolicy::Missing => Some(
+                bins.iter()
+                    .find(|b| b.is_missing)
+                    .map(|b| b.woe)
+                    .unwrap_or(0.0),
+            ),
+            UnseenPolicy::FixedWoe(woe) => Some(woe),
+            UnseenPolicy::MostPopulated => Some(
+                regular
+                    .max_by(|a, b| a.total_weight().total_cmp(&b.total_weight()))
+                    .map_or(0.0, |b| b.woe),
+            ),
+            UnseenPolicy::MostConservative => Some(
+                regular
+                    .max_by(|a, b| a.event_rate().total_cmp(&b.event_rate()))
+                    .map_or(0.0, |b| b.woe),
+            ),
+            UnseenPolicy::Raise => None,
+        }
+    }
+
+    pub fn execute_transform(
+        x_view: &[i32],
+        bins: &[CatBin],
+        config: &CategoricalConfig,
+    ) -> Result<(Vec<f64>, usize), BinningError> {
+        let unseen_woe = Self::unseen_woe(bins, config);
+
+        let mut n_unseen = 0;
+        let mut output = Vec::with_capacity(x_view.len());
+        let positions = Self::bin_positions(x_view, bins, config.missing_code);
+        for (&val, position) in x_view.iter().zip(positions) {
+            if let Some(b) = position {
+                output.push(bins[b].woe);
+            } else if val == config.missing_code {
+                output.push(0.0);
+            } else {
+                n_unseen += 1;
+                output.push(unseen_woe.ok_or(BinningError::UnseenCategory(val))?);
+            }
+        }
+        Ok((output, n_unseen))
+    }
+
+    /// Position in `bins` of the bin each value falls in; `None` for unseen
+    /// categories and for missing values when there is no missing bin.
+    pub(crate) fn bin_positions(
+        x_view: &[i32],
+        bins: &[CatBin],
+        missing_code: i32,
+    ) -> Vec<Option<usize>> {
+        let mut lookup: HashMap<i32, usize> = HashMap::with_capacity(bins.len() * 2);
+        let mut missing = None;
+        for (b, bin) in bins.iter().enumerate() {
+            if bin.is_missing {
+                missing = Some(b);
+            } else {
+                for &id in &bin.indices {
+                    lookup.insert(id, b);
+                }
+            }
+        }
+        x_view
+            .iter()
+            .map(|val| {
+                if *val == missing_code {
+                    missing
+                } else {
+                    lookup.get(val).copied()
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{EventLimits, Separation};
+    use crate::testutil::categories;
+
+    fn fit(x: &[i32], y: &[i32]) -> CategoricalModel {
+        fit_with(CategoricalConfig::new(5, 0.05, 0.4).unwrap(), x, y)
+    }
+
+    fn fit_with(config: CategoricalConfig, x: &[i32], y: &[i32]) -> CategoricalModel {
+        CategoricalBinning::new(config).fit(x, y).unwrap()
+    }
+
+    #[test]
+    fn json_round_trip_keeps_bins_and_transform() {
+        let (x, y) = categories(3000, 4);
+        let model = fit(&x, &y);
+        let json = model.to_json().unwrap();
+        let loaded = CategoricalModel::from_json(&json).unwrap();
+        assert_eq!(loaded.to_json().unwrap(), json);
+        assert_eq!(model.transform(&x).unwrap(), loaded.transform(&x).unwrap());
+    }
+
+    #[test]
+    fn special_codes_stay_out_of_the_dp() {
+        let (x, y) = categories(3000, 4);
+        let plain = fit(&x, &y);
+
+        // A third of the rows, all events, on a special code, and missing
+        // rows moved to another sentinel: neither may change the regular bins.
+        let mut xs: Vec<i32> = x.iter().map(|&c| if c == -1 { -9 } else { c }).collect();
+        let mut ys = y.clone();
+        xs.extend([99; 1500]);
+        ys.extend([1; 1500]);
+        let config = CategoricalConfig::new(5, 0.05, 0.4)
+            .unwrap()
+            .with_missing_code(-9)
+            .unwrap()
+            .with_special_codes(vec![99])
+            .unwrap();
+        let special = CategoricalBinning::new(config).fit(&xs, &ys).unwrap();
+
+        let regular = |m: &CategoricalModel| -> Vec<(Vec<i32>, i32, i32)> {
+            m.bins
+                .iter()
+                .filter(|b| !b.is_missing && !b.is_special)
+                .map(|b| (b.indices.clone(), b.pos, b.neg))
+                .collect()
+        };
+        assert_eq!(regular(&special), regular(&plain));
+        let missing = |m: &CategoricalModel| {
+            let b = m.bins.iter().find(|b| b.is_missing).unwrap();
+            (b.pos, b.neg)
+        };
+        assert_eq!(missing(&special), missing(&plain));
+        let special_bin = special.bins.iter().find(|b| b.is_special).unwrap();
+        assert_eq!(
+            (special_bin.indices.clone(), special_bin.pos),
+            (vec![99], 1500)
+        );
+    }
+
+    #[test]
+    fn unseen_policies_score_from_regular_bins() {
+        // The special code is both the largest bin and the one with the
+        // highest event rate, so it must not be picked by either policy.
+        let (mut x, mut y) = categories(3000, 4);
+        x.extend([99; 1500]);
+        y.extend([1; 1500]);
+        let config = CategoricalConfig::new(5, 0.05, 0.4)
+            .unwrap()
+            .with_special_codes(vec![99])
+            .unwrap();
+        let mut model = CategoricalBinning::new(config).fit(&x, &y).unwrap();
+        let regular: Vec<&CatBin> = model
+            .bins
+            .iter()
+            .filter(|b| !b.is_missing && !b.is_special)
+            .collect();
+        let largest = regular
+            .iter()
+            .max_by(|a, b| a.total_weight().total_cmp(&b.total_weight()))
+            .unwrap()
+            .woe;
+        let riskiest = regular
+            .iter()
+            .max_by(|a, b| a.event_rate().total_cmp(&b.event_rate()))
+            .unwrap()
+            .woe;
+        let missing = model.bins.iter().find(|b| b.is_missing).unwrap().woe;
+
+        let new_x = [0, 42, 3, 43, 42];
+        for (policy, expected) in [
+            (UnseenPolicy::Missing, missing),
+            (UnseenPolicy::FixedWoe(0.7), 0.7),
+            (UnseenPolicy::MostPopulated, largest),
+            (UnseenPolicy::MostConservative, riskiest),
+        ] {
+            model.config = model.config.clone().with_unseen(policy).unwrap();
+            let (woe, n_unseen) = model.transform_with_unseen(&new_x).unwrap();
+            assert_eq!(n_unseen, 3, "{:?}", policy);
+            assert_eq!([woe[1], woe[3], woe[4]], [expected; 3], "{:?}", policy);
+            assert_eq!(woe[0], model.transform(&[0]).unwrap()[0]);
+        }
+
+        model.config = model
+            .config
+            .clone()
+            .with_unseen(UnseenPolicy::Raise)
+            .unwrap();
+        assert_eq!(
+            model.transform(&new_x).unwrap_err(),
+            BinningError::UnseenCategory(42)
+        );
+        assert_eq!(model.transform_with_unseen(&[0, 3]).unwrap().1, 0);
+    }
+
+    fn assert_counts_match(model: &CategoricalModel, x: &[i32], y: &[i32]) {
+        let recount = model.evaluate(x, y).unwrap();
+        for (fitted, new) in model.bins.iter().zip(&recount.bins) {
+            assert_eq!((fitted.pos, fitted.neg), (new.pos, new.neg));
+        }
+    }
+
+    #[test]
+    fn edited_bins_counts_match_recount() {
+        let (x, y) = categories(5000, 7);
+        let mut model = fit(&x, &y);
+        let n_bins = model.bins.len();
+        model.merge_bins(0).unwrap();
+        assert_eq!(model.bins.len(), n_bins - 1);
+        assert_counts_match(&model, &x, &y);
+
+        let category = model.bins[0].indices[0];
+        model.move_category(category, 1).unwrap();
+        assert!(model.bins[1].indices.contains(&category));
+        assert_counts_match(&model, &x, &y);
+    }
+
+    #[test]
+    fn loaded_models_can_be_edited() {
+        let (x, y) = categories(5000, 7);
+        let mut model = fit(&x, &y);
+        let mut loaded = CategoricalModel::from_json(&model.to_json().unwrap()).unwrap();
+        assert_eq!(loaded.trend, Some(Trend::Increasing));
+        model.merge_bins(0).unwrap();
+        loaded.merge_bins(0).unwrap();
+        assert_eq!(loaded.to_json().unwrap(), model.to_json().unwrap());
+
+        loaded.prebins = None;
+        let json = loaded.to_json().unwrap();
+        let mut loaded = CategoricalModel::from_json(&json).unwrap();
+        assert!(matches!(
+            loaded.merge_bins(0),
+            Err(BinningError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn violations_follow_the_fitted_trend() {
+        let (x, y) = categories(5000, 7);
+        let mut model = fit(&x, &y);
+        let n_regular = model
+            .bins
+            .iter()
+            .filter(|b| !b.is_missing && !b.is_special)
+            .count();
+        assert!(n_regular > 2);
+        assert_eq!(model.trend, Some(Trend::Increasing));
+        assert!(model.violations().is_empty());
+
+        model.trend = Some(Trend::Decreasing);
+        let violations = model.violations();
+        assert_eq!(violations.len(), n_regular - 1);
+        assert!(violations.iter().all(|v| matches!(
+            v,
+            Violation::Trend {
+                trend: Trend::Decreasing,
+                ..
+            }
+        )));
+
+        model.trend = None;
+        assert!(model.violations().is_empty());
+    }
+
+    #[test]
+    fn event_limits_bound_every_bin_or_fail() {
+        let (x, y) = categories(5000, 3);
+        let n_nonevents = x
+            .iter()
+            .zip(&y)
+            .filter(|&(&c, &v)| c != -1 && v == 0)
+            .count() as i32;
+        let limits = EventLimits {
+            min_bin_n_event: Some(30),
+            min_bin_n_nonevent: Some(30),
+            ..EventLimits::default()
+        };
+        let config = CategoricalConfig::new(5, 0.02, 0.6)
+            .unwrap()
+            .with_event_limits(limits)
+            .unwrap();
+        let model = CategoricalBinning::new(config.clone()).fit(&x, &y).unwrap();
+        for bin in model.bins.iter().filter(|b| !b.is_missing && !b.is_special) {
+            assert!(bin.pos >= 30 && bin.neg >= 30, "{:?}", bin);
+        }
+
+        let too_many = EventLimits {
+            min_bin_n_nonevent: Some(n_nonevents + 1),
+            ..EventLimits::default()
+        };
+        let err = CategoricalBinning::new(config.clone().with_event_limits(too_many).unwrap())
+            .fit(&x, &y)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            BinningError::InvalidInput(format!(
+                "min_bin_n_nonevent ({}) exceeds the {} non-events in the data",
+                n_nonevents + 1,
+                n_nonevents
+            ))
+        );
+
+        let too_few = EventLimits {
+            max_bin_n_event: Some(1),
+            ..EventLimits::default()
+        };
+        let err = CategoricalBinning::new(config.with_event_limits(too_few).unwrap())
+            .fit(&x, &y)
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("No binning satisfies the event count limits"),
+            "{}",
+            err
+        );
+    }
+
+    /// Smallest WoE and event rate steps between adjacent regular bins.
+    fn min_steps(model: &CategoricalModel) -> (f64, f64) {
+        let regular: Vec<&CatBin> = model
+            .bins
+            .iter()
+            .filter(|b| !b.is_missing && !b.is_special)
+            .collect();
+        regular
+            .windows(2)
+            .fold((f64::INFINITY, f64::INFINITY), |(woe, rate), w| {
+                (
+                    woe.min(w[1].woe - w[0].woe),
+                    rate.min(w[1].event_rate() - w[0].event_rate()),
+                )
+            })
+    }
+
+    #[test]
+    fn adjacent_bins_keep_the_minimum_separation() {
+        let (x, y) = categories(5000, 9);
+        let config = CategoricalConfig::new(8, 0.02, 0.6).unwrap();
+        let (woe_step, rate_step) = min_steps(&fit_with(config.clone(), &x, &y));
+        let separation = Separation {
+            min_woe_diff: Some(2.0 * woe_step + 0.05),
+            min_event_rate_diff: Some(2.0 * rate_step + 0.01),
+        };
+        let model = fit_with(config.with_separation(separation).unwrap(), &x, &y);
+        assert!(
+            model
+                .bins
+                .iter()
+                .filter(|b| !b.is_missing && !b.is_special)
+                .count()
+                > 1
+        );
+        let (woe_step, rate_step) = min_steps(&model);
+        assert!(woe_step >= separation.min_woe_diff.unwrap());
+        assert!(rate_step >= separation.min_event_rate_diff.unwrap());
+    }
+
+    #[test]
+    fn stability_checks_x_the_same_with_or_without_y() {
+        let (x, y) = categories(3000, 4);
+        let model = fit(&x, &y);
+        let missing = vec![-1; 50];
+        let without = model.stability(&missing, None).unwrap();
+        let with = model.stability(&missing, Some(&[0; 50])).unwrap();
+        assert_eq!(with.psi, without.psi);
+        let bin = with.bins.iter().position(|b| b.actual_pct == 1.0).unwrap();
+        assert!(model.bins[bin].is_missing);
+
+        assert_eq!(model.stability(&[], None), Err(BinningError::EmptyInput));
+        assert_eq!(
+            model.stability(&[], Some(&[])),
+            Err(BinningError::EmptyInput)
+        );
+        assert_eq!(
+            model.stability(&[1, 2], Some(&[1, -1])),
+            Err(BinningError::NonBinaryTarget(-1))
+        );
+
+        // Only non-events: the event rates are still known, the WoE is not.
+        let drift = with.bins[bin].drift.unwrap();
+        assert_eq!(drift.event_rate, 0.0);
+        assert_eq!((drift.woe, drift.iv_drift), (None, None));
+    }
+}
@@ -0,0 +1,983 @@
+use crate::error::BinningError;
+use crate::significance::{self, PValueTest};
+use crate::special::{SpecialGroup, validate_groups};
+use crate::woeiv::Divergence;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NumericalConfig {
+    pub max_bins: usize,
+    pub min_bin_pct: f64,
+    pub max_bin_pct: f64,
+    /// Sentinel values binned on their own, outside the optimized bins.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub special_codes: Vec<SpecialGroup>,
+    /// WoE shape enforced across the regular bins.
+    #[serde(default)]
+    pub monotonic_trend: MonotonicTrend,
+    /// Bin-size penalty traded off against IV in the DP.
+    #[serde(default)]
+    pub regularization: Regularization,
+    #[serde(default)]
+    pub solver: Solver,
+    /// Separation measure the DP maximizes in place of IV.
+    #[serde(default)]
+    pub divergence: Divergence,
+    /// How the candidate cut points fed to the DP are chosen.
+    #[serde(default)]
+    pub prebinning: Prebinning,
+    /// Cut points imposed by the user.
+    #[serde(default, skip_serializing_if = "UserSplits::is_none")]
+    pub user_splits: UserSplits,
+    #[serde(default, skip_serializing_if = "EventLimits::is_none")]
+    pub event_limits: EventLimits,
+    /// Largest p-value allowed between adjacent regular bins.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub significance: Option<Significance>,
+    #[serde(default, skip_serializing_if = "Separation::is_none")]
+    pub separation: Separation,
+}
+
+impl NumericalConfig {
+    pub fn new(max_bins: usize, min_bin_pct: f64, max_bin_pct: f64) -> Result<Self, BinningError> {
+        validate_bins(max_bins, min_bin_pct, max_bin_pct)?;
+        Ok(Self {
+            max_bins,
+            min_bin_pct,
+            max_bin_pct,
+            special_codes: Vec::new(),
+            monotonic_trend: MonotonicTrend::default(),
+            regularization: Regularization::default(),
+            solver: Solver::default(),
+            divergence: Divergence::default(),
+            prebinning: Prebinning::default(),
+            user_splits: UserSplits::default(),
+            event_limits: EventLimits::default(),
+            significance: None,
+            separation: Separation::default(),
+        })
+    }
+
+    pub fn with_separation(mut self, separation: Separation) -> Result<Self, BinningError> {
+        separation.validate()?;
+        self.separation = separation;
+        Ok(self)
+    }
+
+    pub fn with_significance(mut self, significance: Significance) -> Result<Self, BinningError> {
+        significance.validate()?;
+        self.significance = Some(significance);
+        Ok(self)
+    }
+
+    pub fn with_event_limits(mut self, event_limits: EventLimits) -> Result<Self, BinningError> {
+        event_limits.validate()?;
+        self.event_limits = event_limits;
+        Ok(self)
+    }
+
+    /// Sorts the cut points; they must be finite and distinct, and locked ones
+    /// must leave room for at least one bin each within `max_bins`.
+    pub fn with_user_splits(mut self, user_splits: UserSplits) -> Result<Self, BinningError> {
+        let mut user_splits = user_splits;
+        if let UserSplits::Fixed(splits) | UserSplits::Locked(splits) = &mut user_splits {
+            if let Some(v) = splits.iter().find(|v| !v.is_finite()) {
+                return Err(BinningError::InvalidConfig(format!(
+                    "User splits must be finite, found {}",
+                    v
+                )));
+            }
+            splits.sort_by(|a, b| a.total_cmp(b));
+            if let Some(w) = splits.windows(2).find(|w| w[0] == w[1]) {
+                return Err(BinningError::InvalidConfig(format!(
+                    "User split {} appears more than once",
+                    w[0]
+                )));
+            }
+        }
+        if let UserSplits::Locked(splits) = &user_splits
+            && splits.len() >= self.max_bins
+        {
+            return Err(BinningError::InvalidConfig(format!(
+                "{} locked splits need at least {} bins, but max_bins is {}",
+                splits.len(),
+                splits.len() + 1,
+                self.max_bins
+            )));
+        }
+        self.user_splits = user_splits;
+        Ok(self)
+    }
+
+    pub fn with_prebinning(mut self, prebinning: Prebinning) -> Result<Self, BinningError> {
+        prebinning.validate()?;
+        self.prebinning = prebinning;
+        Ok(self)
+    }
+
+    pub fn with_divergence(mut self, divergence: Divergence) -> Self {
+        self.divergence = divergence;
+        self
+    }
+
+    pub fn with_solver(mut self, solver: Solver) -> Self {
+        self.solver = solver;
+        self
+    }
+
+    pub fn with_regularization(
+        mut self,
+        regularization: Regularization,
+    ) -> Result<Self, BinningError> {
+        regularization.validate(self.min_bin_pct, self.max_bin_pct)?;
+        self.regularization = regularization;
+        Ok(self)
+    }
+
+    /// Runs the checks of `new` and the builders on the fields as they are,
+    /// for configs that did not go through them, such as loaded ones.
+    pub fn validate(&self) -> Result<(), BinningError> {
+        let checked = Self::new(self.max_bins, self.min_bin_pct, self.max_bin_pct)?
+            .with_special_codes(self.special_codes.clone())?
+            .with_prebinning(self.prebinning)?
+            .with_regularization(self.regularization)?
+            .with_user_splits(self.user_splits.clone())?
+            .with_event_limits(self.event_limits)?
+            .with_separation(self.separation)?;
+        if checked.user_splits != self.user_splits {
+            return Err(BinningError::InvalidConfig(
+                "User splits must be in ascending order".to_string(),
+            ));
+        }
+        if let Some(significance) = self.significance {
+            significance.validate()?;
+        }
+        Ok(())
+    }
+
+    /// Bin share the size penalty pulls toward.
+    pub fn target_bin_pct(&self) -> f64 {
+        self.regularization
+            .target_pct(self.min_bin_pct, self.max_bin_pct)
+    }
+
+    pub fn with_monotonic_trend(mut self, monotonic_trend: MonotonicTrend) -> Self {
+        self.monotonic_trend = monotonic_trend;
+        self
+    }
+
+    pub fn with_special_codes(mut self, groups: Vec<SpecialGroup>) -> Result<Self, BinningError> {
+        validate_groups(&groups)?;
+        self.special_codes = groups;
+        Ok(self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CategoricalConfig {
+    pub max_bins: usize,
+    pub min_bin_pct: f64,
+    pub max_bin_pct: f64,
+    /// Category code treated as missing.
+    #[serde(default = "default_missing_code")]
+    pub missing_code: i32,
+    /// Categories kept as standalone bins, outside the optimized bins.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub special_codes: Vec<i32>,
+    /// How categories not seen during fit are scored at transform time.
+    #[serde(default)]
+    pub unseen: UnseenPolicy,
+    /// Separation measure the DP maximizes in place of IV.
+    #[serde(default)]
+    pub divergence: Divergence,
+    #[serde(default, skip_serializing_if = "EventLimits::is_none")]
+    pub event_limits: EventLimits,
+    /// Largest p-value allowed between adjacent regular bins.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub significance: Option<Significance>,
+    #[serde(default, skip_serializing_if = "Separation::is_none")]
+    pub separation: Separation,
+}
+
+fn default_missing_code() -> i32 {
+    -1
+}
+
+impl CategoricalConfig {
+    pub fn new(max_bins: usize, min_bin_pct: f64, max_bin_pct: f64) -> Result<Self, BinningError> {
+        validate_bins(max_bins, min_bin_pct, max_bin_pct)?;
+        Ok(Self {
+            max_bins,
+            min_bin_pct,
+            max_bin_pct,
+            missing_code: default_missing_code(),
+            special_codes: Vec::new(),
+            unseen: UnseenPolicy::default(),
+            divergence: Divergence::default(),
+            event_limits: EventLimits::default(),
+            significance: None,
+            separation: Separation::default(),
+        })
+    }
+
+    pub fn with_separation(mut self, separation: Separation) -> Result<Self, BinningError> {
+        separation.validate()?;
+        self.separation = separation;
+        Ok(self)
+    }
+
+    pub fn with_significance(mut self, significance: Significance) -> Result<Self, BinningError> {
+        significance.validate()?;
+        self.significance = Some(significance);
+        Ok(self)
+    }
+
+    pub fn with_event_limits(mut self, event_limits: EventLimits) -> Result<Self, BinningError> {
+        event_limits.validate()?;
+        self.event_limits = event_limits;
+        Ok(self)
+    }
+
+    pub fn with_divergence(mut self, divergence: Divergence) -> Self {
+        self.divergence = divergence;
+        self
+    }
+
+    pub fn with_unseen(mut self, unseen: UnseenPolicy) -> Result<Self, BinningError> {
+        if let UnseenPolicy::FixedWoe(woe) = unseen
+            && !woe.is_finite()
+        {
+            return Err(BinningError::InvalidConfig(format!(
+                "Fixed WoE for unseen categories must be finite, found {}",
+                woe
+            )));
+        }
+        self.unseen = unseen;
+        Ok(self)
+    }
+
+    pub fn with_missing_code(mut self, missing_code: i32) -> Result<Self, BinningError> {
+        self.missing_code = missing_code;
+        self.validate_codes()?;
+        Ok(self)
+    }
+
+    pub fn with_special_codes(mut self, special_codes: Vec<i32>) -> Result<Self, BinningError> {
+        self.special_codes = special_codes;
+        self.validate_codes()?;
+        Ok(self)
+    }
+
+    /// Runs the checks of `new` and the builders on the fields as they are,
+    /// for configs that did not go through them, such as loaded ones.
+    pub fn validate(&self) -> Result<(), BinningError> {
+        Self::new(self.max_bins, self.min_bin_pct, self.max_bin_pct)?
+            .with_unseen(self.unseen)?
+            .with_event_limits(self.event_limits)?
+            .with_separation(self.separation)?;
+        self.validate_codes()?;
+        if let Some(significance) = self.significance {
+            significance.validate()?;
+        }
+        Ok(())
+    }
+
+    fn validate_codes(&self) -> Result<(), BinningError> {
+        for (i, &code) in self.special_codes.iter().enumerate() {
+            if code == self.missing_code {
+                return Err(BinningError::InvalidConfig(format!(
+                    "Special code {} is also the missing code",
+                    code
+                )));
+            }
+            if self.special_codes[..i].contains(&code) {
+                return Err(BinningError::InvalidConfig(format!(
+                    "Special code {} appears more than once",
+                    code
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Settings of `ContinuousBinning`, which bins a real-valued target by the
+/// variance of its mean explained by the bins.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContinuousConfig {
+    pub max_bins: usize,
+    pub min_bin_pct: f64,
+    pub max_bin_pct: f64,
+    /// Shape enforced on the bin means: ascending, descending, the better of
+    /// the two, or none.
+    #[serde(default)]
+    pub monotonic_trend: MonotonicTrend,
+    /// Bin-size penalty traded off against the explained variance share.
+    #[serde(default)]
+    pub regularization: Regularization,
+    /// Quantile or uniform prebins; the other methods need a binary target.
+    #[serde(default)]
+    pub prebinning: Prebinning,
+}
+
+impl ContinuousConfig {
+    pub fn new(max_bins: usize, min_bin_pct: f64, max_bin_pct: f64) -> Result<Self, BinningError> {
+        validate_bins(max_bins, min_bin_pct, max_bin_pct)?;
+        Ok(Self {
+            max_bins,
+            min_bin_pct,
+            max_bin_pct,
+            monotonic_trend: MonotonicTrend::default(),
+            regularization: Regularization::default(),
+            prebinning: Prebinning::default(),
+        })
+    }
+
+    pub fn with_monotonic_trend(
+        mut self,
+        monotonic_trend: MonotonicTrend,
+    ) -> Result<Self, BinningError> {
+        match monotonic_trend {
+            MonotonicTrend::Ascending
+            | MonotonicTrend::Descending
+            | MonotonicTrend::AutoAscDesc
+            | MonotonicTrend::None => {
+                self.monotonic_trend = monotonic_trend;
+                Ok(self)
+            }
+            _ => Err(BinningError::InvalidConfig(
+                "ContinuousBinning supports monotonic_trend 'ascending', 'descending', \
+                 'auto_asc_desc' or 'none'"
+                    .to_string(),
+            )),
+        }
+    }
+
+    pub fn with_regularization(
+        mut self,
+        regularization: Regularization,
+    ) -> Result<Self, BinningError> {
+        regularization.validate(self.min_bin_pct, self.max_bin_pct)?;
+        self.regularization = regularization;
+        Ok(self)
+    }
+
+    pub fn with_prebinning(mut self, prebinning: Prebinning) -> Result<Self, BinningError> {
+        prebinning.validate_unsupervised("ContinuousBinning")?;
+        self.prebinning = prebinning;
+        Ok(self)
+    }
+
+    /// Runs the checks of `new` and the builders on the fields as they are,
+    /// for configs that did not go through them, such as loaded ones.
+    pub fn validate(&self) -> Result<(), BinningError> {
+        Self::new(self.max_bins, self.min_bin_pct, self.max_bin_pct)?
+            .with_monotonic_trend(self.monotonic_trend)?
+            .with_regularization(self.regularization)?
+            .with_prebinning(self.prebinning)?;
+        Ok(())
+    }
+
+    /// Bin share the size penalty pulls toward.
+    pub fn target_bin_pct(&self) -> f64 {
+        self.regularization
+            .target_pct(self.min_bin_pct, self.max_bin_pct)
+    }
+}
+
+/// Settings of `MulticlassBinning`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MulticlassConfig {
+    pub max_bins: usize,
+    pub min_bin_pct: f64,
+    pub max_bin_pct: f64,
+    /// Quantile or uniform prebins; the other methods need a binary target.
+    #[serde(default)]
+    pub prebinning: Prebinning,
+}
+
+impl MulticlassConfig {
+    pub fn new(max_bins: usize, min_bin_pct: f64, max_bin_pct: f64) -> Result<Self, BinningError> {
+        validate_bins(max_bins, min_bin_pct, max_bin_pct)?;
+        Ok(Self {
+            max_bins,
+            min_bin_pct,
+            max_bin_pct,
+            prebinning: Prebinning::default(),
+        })
+    }
+
+    pub fn with_prebinning(mut self, prebinning: Prebinning) -> Result<Self, BinningError> {
+        prebinning.validate_unsupervised("MulticlassBinning")?;
+        self.prebinning = prebinning;
+        Ok(self)
+    }
+
+    /// Runs the checks of `new` and the builders on the fields as they are,
+    /// for configs that did not go through them, such as loaded ones.
+    pub fn validate(&self) -> Result<(), BinningError> {
+        Self::new(self.max_bins, self.min_bin_pct, self.max_bin_pct)?
+            .with_prebinning(self.prebinning)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Solver {
+    /// Keeps one path per bin count and end point.
+    #[default]
+    Fast,
+    /// Proven optimum over the prebins; also reports the fast solver's gap.
+    Exact,
+}
+
+impl FromStr for Solver {
+    type Err = BinningError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fast" => Ok(Solver::Fast),
+            "exact" => Ok(Solver::Exact),
+            other => Err(BinningError::InvalidConfig(format!(
+                "Unknown solver '{}', expected 'fast' or 'exact'",
+                other
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrebinMethod {
+    /// Equal-frequency chunks.
+    #[default]
+    Quantile,
+    /// Equal-width intervals.
+    Uniform,
+    /// Decision-tree splits minimizing Gini impurity.
+    CartGini,
+    /// Decision-tree splits minimizing entropy.
+    CartEntropy,
+    /// Entropy splits kept while they pass the Fayyad-Irani MDL criterion.
+    Mdlp,
+    /// Quantile prebins merged while adjacent pairs are not significantly
+    /// different at 5%.
+    ChiMerge,
+}
+
+impl FromStr for PrebinMethod {
+    type Err = BinningError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "quantile" => Ok(PrebinMethod::Quantile),
+            "uniform" => Ok(PrebinMethod::Uniform),
+            "cart_gini" => Ok(PrebinMethod::CartGini),
+            "cart_entropy" => Ok(PrebinMethod::CartEntropy),
+            "mdlp" => Ok(PrebinMethod::Mdlp),
+            "chi_merge" => Ok(PrebinMethod::ChiMerge),
+            other => Err(BinningError::InvalidConfig(format!(
+                "Unknown prebinning method '{}', expected one of 'quantile', 'uniform', \
+                 'cart_gini', 'cart_entropy', 'mdlp', 'chi_merge'",
+                other
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Prebinning {
+    #[serde(default)]
+    pub method: PrebinMethod,
+    /// Prebin count for quantile and uniform, maximum for the others. Defaults
+    /// to `sqrt(n)` clamped to 100..500; MDLP and ChiMerge are then bounded by
+    /// their stopping rules alone.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n_bins: Option<usize>,
+}
+
+impl Prebinning {
+    fn validate(&self) -> Result<(), BinningError> {
+        if let Some(n_bins) = self.n_bins
+            && n_bins < 2
+        {
+            return Err(BinningError::InvalidConfig(format!(
+                "The number of prebins must be at least 2, found {}",
+                n_bins
+            )));
+        }
+        Ok(())
+    }
+
+    /// Also rejects the methods that split on a binary target, for binners
+    /// of other targets.
+    fn validate_unsupervised(&self, binner: &str) -> Result<(), BinningError> {
+        self.validate()?;
+        if !matches!(self.method, PrebinMethod::Quantile | PrebinMethod::Uniform) {
+            return Err(BinningError::InvalidConfig(format!(
+                "{} supports prebinning 'quantile' or 'uniform' only",
+                binner
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Requires adjacent regular bins to differ in event rate, by `test` on
+/// their unweighted counts, at p-value `max_pvalue` or below.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Significance {
+    pub max_pvalue: f64,
+    #[serde(default)]
+    pub test: PValueTest,
+}
+
+impl Significance {
+    fn validate(&self) -> Result<(), BinningError> {
+        if !(self.max_pvalue > 0.0 && self.max_pvalue <= 1.0) {
+            return Err(BinningError::InvalidConfig(format!(
+                "max_pvalue must be in (0, 1], found {}",
+                self.max_pvalue
+            )));
+        }
+        Ok(())
+    }
+
+    /// Whether bins with `(pos, neg)` counts `a` and `b` differ significantly.
+    #[inline]
+    pub fn separates(&self, a: (i32, i32), b: (i32, i32)) -> bool {
+        significance::pvalue(self.test, a, b) <= self.max_pvalue
+    }
+}
+
+/// Smallest WoE and event rate differences between adjacent regular bins,
+/// so that no two neighbours are near duplicates.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Separation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_event_rate_diff: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_woe_diff: Option<f64>,
+}
+
+impl Separation {
+    pub fn is_none(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether bins with `(woe, event_rate)` of `prev` and `cur` are far
+    /// enough apart.
+    #[inline]
+    pub fn separates(&self, prev: (f64, f64), cur: (f64, f64)) -> bool {
+        self.min_woe_diff
+            .is_none_or(|min| (cur.0 - prev.0).abs() >= min)
+            && self
+                .min_event_rate_diff
+                .is_none_or(|min| (cur.1 - prev.1).abs() >= min)
+    }
+
+    fn validate(&self) -> Result<(), BinningError> {
+        for (name, diff) in [
+            ("min_event_rate_diff", self.min_event_rate_diff),
+            ("min_woe_diff", self.min_woe_diff),
+        ] {
+            if let Some(diff) = diff
+                && !(diff.is_finite() && diff >= 0.0)
+            {
+                return Err(BinningError::InvalidConfig(format!(
+                    "{} must be a non-negative number, found {}",
+                    name, diff
+                )));
+            }
+        }
+        if let Some(diff) = self.min_event_rate_diff
+            && diff > 1.0
+        {
+            return Err(BinningError::InvalidConfig(format!(
+                "min_event_rate_diff must not exceed 1, found {}",
+                diff
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Unweighted event (`y == 1`) and non-event counts each regular bin must
+/// respect, so that no bin's WoE rests on the 0.5 pseudo-count.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct EventLimits {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_bin_n_event: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_bin_n_nonevent: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bin_n_event: Option<i32>,
+}
+
+impl EventLimits {
+    pub fn is_none(&self) -> bool {
+        *self == Self::default()
+    }
+
+    #[inline]
+    pub fn allows(&self, pos: i32, neg: i32) -> bool {
+        self.min_bin_n_event.is_none_or(|min| pos >= min)
+            && self.min_bin_n_nonevent.is_none_or(|min| neg >= min)
+            && self.max_bin_n_event.is_none_or(|max| pos <= max)
+    }
+
+    fn validate(&self) -> Result<(), BinningError> {
+        for (name, limit) in [
+            ("min_bin_n_event", self.min_bin_n_event),
+            ("min_bin_n_nonevent", self.min_bin_n_nonevent),
+            ("max_bin_n_event", self.max_bin_n_event),
+        ] {
+            if let Some(limit) = limit
+                && limit < 0
+            {
+                return Err(BinningError::InvalidConfig(format!(
+                    "{} must be non-negative, found {}",
+                    name, limit
+                )));
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min_bin_n_event, self.max_bin_n_event)
+            && min > max
+        {
+            return Err(BinningError::InvalidConfig(format!(
+                "min_bin_n_event ({}) must not exceed max_bin_n_event ({})",
+                min, max
+            )));
+        }
+        Ok(())
+    }
+
+    /// Error for when no binning satisfies the limits, naming the limit the
+    /// data as a whole already breaks where there is one.
+    pub(crate) fn infeasible(&self, total_pos: i32, total_neg: i32) -> BinningError {
+        if let Some(min) = self.min_bin_n_event
+            && total_pos < min
+        {
+            return BinningError::InvalidInput(format!(
+                "min_bin_n_event ({}) exceeds the {} events in the data",
+                min, total_pos
+            ));
+        }
+        if let Some(min) = self.min_bin_n_nonevent
+            && total_neg < min
+        {
+            return BinningError::InvalidInput(format!(
+                "min_bin_n_nonevent ({}) exceeds the {} non-events in the data",
+                min, total_neg
+            ));
+        }
+        BinningError::InvalidInput(
+            "No binning satisfies the event count limits together with the other constraints"
+                .to_string(),
+        )
+    }
+}
+
+/// A cut point `s` separates `x <= s` from `x > s`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "mode", content = "splits", rename_all = "snake_case")]
+pub enum UserSplits {
+    #[default]
+    None,
+    /// The bins are exactly these cut points; the DP is skipped.
+    Fixed(Vec<f64>),
+    /// The DP keeps these cut points and optimizes the rest around them.
+    Locked(Vec<f64>),
+}
+
+impl UserSplits {
+    pub fn is_none(&self) -> bool {
+        matches!(self, UserSplits::None)
+    }
+
+    pub fn splits(&self) -> &[f64] {
+        match self {
+            UserSplits::None => &[],
+            UserSplits::Fixed(splits) | UserSplits::Locked(splits) => splits,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PenaltyFamily {
+    None,
+    /// `-ln(1 - r^2)`, where `r` is the distance of the bin share from the
+    /// target, relative to half the allowed range.
+    #[default]
+    LogBarrier,
+    /// `r^2`, with `r` as for `LogBarrier`.
+    Quadratic,
+    /// `p ln p` of the bin share `p`, so the total rewards the entropy of
+    /// bin sizes.
+    Entropy,
+}
+
+impl FromStr for PenaltyFamily {
+    type Err = BinningError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(PenaltyFamily::None),
+            "log_barrier" => Ok(PenaltyFamily::LogBarrier),
+            "quadratic" => Ok(PenaltyFamily::Quadratic),
+            "entropy" => Ok(PenaltyFamily::Entropy),
+            other => Err(BinningError::InvalidConfig(format!(
+                "Unknown penalty '{}', expected one of 'none', 'log_barrier', 'quadratic', \
+                 'entropy'",
+                other
+            ))),
+        }
+    }
+}
+
+/// The k-th bin of a solution with at most `k_max` bins is charged
+/// `strength * (k_max - k + 1) / k_max` times the family's penalty.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Regularization {
+    pub family: PenaltyFamily,
+    pub strength: f64,
+    /// Defaults to the midpoint of `min_bin_pct` and `max_bin_pct`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_bin_pct: Option<f64>,
+}
+
+impl Default for Regularization {
+    fn default() -> Self {
+        Self {
+            family: PenaltyFamily::LogBarrier,
+            strength: 5.0,
+            target_bin_pct: None,
+        }
+    }
+}
+
+impl Regularization {
+    fn validate(&self, min_bin_pct: f64, max_bin_pct: f64) -> Result<(), BinningError> {
+        if !self.strength.is_finite() || self.strength < 0.0 {
+            return Err(BinningError::InvalidConfig(format!(
+                "Penalty strength must be finite and non-negative, found {}",
+                self.strength
+            )));
+        }
+        if let Some(target) = self.target_bin_pct
+            && !(min_bin_pct..=max_bin_pct).contains(&target)
+        {
+            return Err(BinningError::InvalidConfig(format!(
+                "target_bin_pct ({}) must lie between min_bin_pct ({}) and max_bin_pct ({})",
+                target, min_bin_pct, max_bin_pct
+            )));
+        }
+        Ok(())
+    }
+
+    fn target_pct(&self, min_bin_pct: f64, max_bin_pct: f64) -> f64 {
+        self.target_bin_pct
+            .unwrap_or((min_bin_pct + max_bin_pct) / 2.0)
+    }
+
+    /// Unscaled penalty of a bin holding `pct` of the rows.
+    #[inline]
+    pub(crate) fn penalty(&self, pct: f64, target_pct: f64, range_width: f64) -> f64 {
+        match self.family {
+            PenaltyFamily::None => 0.0,
+            PenaltyFamily::LogBarrier => {
+                let ratio = ((pct - target_pct).abs() / range_width).min(0.999);
+                -(1.0 - ratio.powi(2)).ln()
+            }
+            PenaltyFamily::Quadratic => ((pct - target_pct) / range_width).powi(2),
+            PenaltyFamily::Entropy => {
+                if pct > 0.0 {
+                    pct * pct.ln()
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MonotonicTrend {
+    Ascending,
+    Descending,
+    Peak,
+    Valley,
+    /// Best of ascending, descending, peak and valley by IV.
+    Auto,
+    /// Best of ascending and descending by IV.
+    #[default]
+    AutoAscDesc,
+    /// A single trend guessed from the shape of the prebin WoE.
+    AutoHeuristic,
+    /// Unconstrained IV maximization.
+    None,
+}
+
+impl FromStr for MonotonicTrend {
+    type Err = BinningError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ascending" => Ok(MonotonicTrend::Ascending),
+            "descending" => Ok(MonotonicTrend::Descending),
+            "peak" => Ok(MonotonicTrend::Peak),
+            "valley" => Ok(MonotonicTrend::Valley),
+            "auto" => Ok(MonotonicTrend::Auto),
+            "auto_asc_desc" => Ok(MonotonicTrend::AutoAscDesc),
+            "auto_heuristic" => Ok(MonotonicTrend::AutoHeuristic),
+            "none" => Ok(MonotonicTrend::None),
+            other => Err(BinningError::InvalidConfig(format!(
+                "Unknown monotonic_trend '{}', expected one of 'ascending', 'descending', \
+                 'peak', 'valley', 'auto', 'auto_asc_desc', 'auto_heuristic', 'none'",
+                other
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "policy", content = "woe", rename_all = "snake_case")]
+pub enum UnseenPolicy {
+    /// Score as the missing bin, or 0.0 if there is none.
+    #[default]
+    Missing,
+    FixedWoe(f64),
+    /// Score as the regular bin with the largest (weighted) population.
+    MostPopulated,
+    /// Score as the regular bin with the highest event rate.
+    MostConservative,
+    Raise,
+}
+
+impl FromStr for UnseenPolicy {
+    type Err = BinningError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "missing" => Ok(UnseenPolicy::Missing),
+            "most_populated" => Ok(UnseenPolicy::MostPopulated),
+            "most_conservative" => Ok(UnseenPolicy::MostConservative),
+            "raise" => Ok(UnseenPolicy::Raise),
+            other => Err(BinningError::InvalidConfig(format!(
+                "Unknown unseen policy '{}', expected one of 'missing', 'most_populated', \
+                 'most_conservative', 'raise' or a fixed WoE value",
+                other
+            ))),
+        }
+    }
+}
+
+fn validate_bins(max_bins: usize, min_bin_pct: f64, max_bin_pct: f64) -> Result<(), BinningError> {
+    if max_bins < 1 {
+        return Err(BinningError::InvalidConfig(
+            "max_bins must be at least 1".to_string(),
+        ));
+    }
+    if min_bin_pct >= max_bin_pct {
+        return Err(BinningError::InvalidConfig(format!(
+            "Invalid constraints: min_bin_pct ({}) must be less than max_bin_pct ({})",
+            min_bin_pct, max_bin_pct
+        )));
+    }
+    if !(0.0..=1.0).contains(&min_bin_pct) || !(0.0..=1.0).contains(&max_bin_pct) {
+        return Err(BinningError::InvalidConfig(
+            "Bin percentages must be in the range [0.0, 1.0]".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_max_bins_is_rejected() {
+        assert!(matches!(
+            NumericalConfig::new(0, 0.05, 0.5),
+            Err(BinningError::InvalidConfig(_))
+        ));
+        assert!(matches!(
+            CategoricalConfig::new(0, 0.05, 0.5),
+            Err(BinningError::InvalidConfig(_))
+        ));
+        assert!(NumericalConfig::new(1, 0.05, 0.5).is_ok());
+    }
+
+    #[test]
+    fn non_finite_bin_pcts_are_rejected() {
+        for (min, max) in [
+            (f64::NAN, 0.5),
+            (0.05, f64::NAN),
+            (f64::NEG_INFINITY, 0.5),
+            (0.05, f64::INFINITY),
+        ] {
+            assert!(
+                matches!(
+                    NumericalConfig::new(5, min, max),
+                    Err(BinningError::InvalidConfig(_))
+                ),
+                "({}, {})",
+                min,
+                max
+            );
+        }
+    }
+
+    #[test]
+    fn continuous_config_rejects_unsupported_settings() {
+        let config = ContinuousConfig::new(5, 0.05, 0.5).unwrap();
+        assert!(matches!(
+            config.clone().with_monotonic_trend(MonotonicTrend::Peak),
+            Err(BinningError::InvalidConfig(_))
+        ));
+        assert!(matches!(
+            config.clone().with_prebinning(Prebinning {
+                method: PrebinMethod::Mdlp,
+                n_bins: None,
+            }),
+            Err(BinningError::InvalidConfig(_))
+        ));
+        let mut loaded = config.with_monotonic_trend(MonotonicTrend::None).unwrap();
+        assert!(loaded.validate().is_ok());
+        loaded.monotonic_trend = MonotonicTrend::AutoHeuristic;
+        assert!(loaded.validate().is_err());
+    }
+
+    #[test]
+    fn penalty_families_follow_their_formulas() {
+        let reg = |family| Regularization {
+            family,
+            strength: 1.0,
+            target_bin_pct: None,
+        };
+        // Target 0.2, half-range 0.1: a 0.25 share sits halfway to the edge.
+        let (target, width) = (0.2, 0.1);
+        assert_eq!(reg(PenaltyFamily::None).penalty(0.25, target, width), 0.0);
+        for family in [PenaltyFamily::LogBarrier, PenaltyFamily::Quadratic] {
+            assert_eq!(reg(family).penalty(target, target, width), 0.0);
+        }
+        let log_barrier = reg(PenaltyFamily::LogBarrier).penalty(0.25, target, width);
+        assert!((log_barrier - -(0.75f64).ln()).abs() < 1e-12);
+        // Capped short of the barrier beyond the allowed range.
+        assert!(
+            reg(PenaltyFamily::LogBarrier)
+                .penalty(0.9, target, width)
+                .is_finite()
+        );
+        let quadratic = reg(PenaltyFamily::Quadratic).penalty(0.25, target, width);
+        assert!((quadratic - 0.25).abs() < 1e-12);
+        let entropy = reg(PenaltyFamily::Entropy).penalty(0.25, target, width);
+        assert!((entropy - 0.25 * 0.25f64.ln()).abs() < 1e-12);
+        assert_eq!(reg(PenaltyFamily::Entropy).penalty(0.0, target, width), 0.0);
+    }
+}
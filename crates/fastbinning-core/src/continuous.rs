@@ -0,0 +1,434 @@
+use crate::config::{ContinuousConfig, MonotonicTrend};
+use crate::error::BinningError;
+use crate::json;
+use crate::numerical::Trend;
+use crate::prebinning::prebin_ends;
+use crate::precontinuous::{PreContBinStats, TargetSummary};
+use crate::validate;
+use ndarray::{Array2, ArrayView1};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContBin {
+    pub bin_id: usize,
+    #[serde(with = "json::float_pair")]
+    pub range: (f64, f64),
+    pub count: i32,
+    #[serde(with = "json::float")]
+    pub sum: f64,
+    #[serde(with = "json::float")]
+    pub mean: f64,
+    #[serde(with = "json::float")]
+    pub std: f64,
+    #[serde(with = "json::float")]
+    pub min: f64,
+    #[serde(with = "json::float")]
+    pub max: f64,
+    pub is_missing: bool,
+}
+
+impl ContBin {
+    fn from_summary(bin_id: usize, range: (f64, f64), s: &TargetSummary, is_missing: bool) -> Self {
+        Self {
+            bin_id,
+            range,
+            count: s.count,
+            sum: s.sum,
+            mean: s.mean(),
+            std: s.std(),
+            min: s.min,
+            max: s.max,
+            is_missing,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContinuousModel {
+    pub config: ContinuousConfig,
+    pub bins: Vec<ContBin>,
+}
+
+impl ContinuousModel {
+    pub fn transform(&self, x: ArrayView1<f64>) -> Vec<f64> {
+        ContinuousBinning::execute_transform(x, &self.bins)
+    }
+
+    pub fn to_json(&self) -> Result<String, BinningError> {
+        json::to_json("continuous", self)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, BinningError> {
+        let model: Self = json::from_json("continuous", json)?;
+        model.config.validate()?;
+        Ok(model)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ContinuousBinning {
+    pub config: ContinuousConfig,
+}
+
+impl ContinuousBinning {
+    pub fn new(config: ContinuousConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn fit(
+        &self,
+        x: ArrayView1<f64>,
+        y: ArrayView1<f64>,
+    ) -> Result<ContinuousModel, BinningError> {
+        Ok(ContinuousModel {
+            config: self.config.clone(),
+            bins: self.execute_fit(x, y)?,
+        })
+    }
+
+    pub fn execute_fit(
+        &self,
+        x: ArrayView1<f64>,
+        y: ArrayView1<f64>,
+    ) -> Result<Vec<ContBin>, BinningError> {
+        validate::check_length("y", x.len(), y.len())?;
+        validate::check_numerical(x.iter())?;
+        if y.iter().any(|v| !v.is_finite()) {
+            return Err(BinningError::InvalidInput(
+                "Target y must contain only finite values".to_string(),
+            ));
+        }
+
+        let stats = self.prebinning(x, y);
+        let best = self
+            .candidate_trends()
+            .into_iter()
+            .filter_map(|trend| self.split(&stats, trend))
+            .max_by(|a, b| a.0.total_cmp(&b.0));
+        match best {
+            Some((_, splits)) => Ok(self.reconstruct_bins(&stats, splits)),
+            None => Err(BinningError::InvalidInput(
+                "No binning satisfies min_bin_pct and max_bin_pct together with the monotonic \
+                 trend"
+                    .to_string(),
+            )),
+        }
+    }
+
+    fn candidate_trends(&self) -> Vec<Trend> {
+        match self.config.monotonic_trend {
+            MonotonicTrend::Ascending => vec![Trend::Increasing],
+            MonotonicTrend::Descending => vec![Trend::Decreasing],
+            MonotonicTrend::None => vec![Trend::Unconstrained],
+            // `AutoAscDesc`, the only other trend a validated config holds.
+            _ => vec![Trend::Increasing, Trend::Decreasing],
+        }
+    }
+
+    fn prebinning(&self, x: ArrayView1<f64>, y: ArrayView1<f64>) -> PreContBinStats {
+        let mut missing = TargetSummary::default();
+        let mut data: Vec<(f64, f64)> = Vec::with_capacity(x.len());
+        for (&v, &t) in x.iter().zip(y.iter()) {
+            if v.is_nan() {
+                missing.push(t);
+                continue;
+            }
+            data.push((v, t));
+        }
+
+        data.par_sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut prebins: Vec<TargetSummary> = Vec::new();
+        let mut edges: Vec<f64> = Vec::new();
+        let mut start = 0;
+        for end in prebin_ends(&self.config.prebinning, &data) {
+            let mut curr = TargetSummary::default();
+            data[start..=end].iter().for_each(|&(_, t)| curr.push(t));
+            prebins.push(curr);
+            edges.push(data[end].0);
+            start = end + 1;
+        }
+        PreContBinStats::new(prebins, edges, missing)
+    }
+
+    /// Best penalized explained-variance share under `trend` and its splits,
+    /// or `None` when no binning satisfies the size bounds.
+    fn split(&self, stats: &PreContBinStats, trend: Trend) -> Option<(f64, Vec<usize>)> {
+        let n = stats.edges.len();
+        let k_max = self.config.max_bins.min(n);
+        let total_samples = stats.total_count as f64;
+        let min_samples = (total_samples * self.config.min_bin_pct) as i32;
+        let max_samples = (total_samples * self.config.max_bin_pct) as i32;
+        let regularization = &self.config.regularization;
+        let target_pct = self.config.target_bin_pct();
+        let range_width = (self.config.max_bin_pct - self.config.min_bin_pct) / 2.0;
+        let lambda = regularization.strength;
+
+        let mut dp_score = Array2::<f64>::from_elem((k_max + 1, n), f64::NEG_INFINITY);
+        let mut last_mean = Array2::<f64>::from_elem((k_max + 1, n), 0.0);
+        let mut best_split = Array2::<usize>::from_elem((k_max + 1, n), 0);
+
+        for i in 0..n {
+            let (count, sum) = stats.get_count_sum(0, i);
+            if count >= min_samples && count <= max_samples {
+                let pct = count as f64 / total_samples;
+                let penalty = lambda * regularization.penalty(pct, target_pct, range_width);
+                dp_score[[1, i]] = stats.calc_r2_range(0, i) - penalty;
+                last_mean[[1, i]] = sum / count as f64;
+            }
+        }
+
+        for k in 2..=k_max {
+            let adaptive_lambda = lambda * ((k_max - k + 1) as f64 / k_max as f64);
+
+            for i in (k - 1)..n {
+                for j in (k - 2)..i {
+                    if dp_score[[k - 1, j]] == f64::NEG_INFINITY {
+                        continue;
+                    }
+
+                    let (cur_count, cur_sum) = stats.get_count_sum(j + 1, i);
+                    if cur_count < min_samples || cur_count > max_samples {
+                        continue;
+                    }
+
+                    let cur_mean = cur_sum / cur_count as f64;
+                    let prev_mean = last_mean[[k - 1, j]];
+                    if !trend.allows(0, 0, prev_mean, cur_mean) {
+                        continue;
+                    }
+
+                    let cur_pct = cur_count as f64 / total_samples;
+                    let penalty =
+                        adaptive_lambda * regularization.penalty(cur_pct, target_pct, range_width);
+                    let total_score =
+                        dp_score[[k - 1, j]] + stats.calc_r2_range(j + 1, i) - penalty;
+                    if total_score > dp_score[[k, i]] {
+                        dp_score[[k, i]] = total_score;
+                        best_split[[k, i]] = j;
+                        last_mean[[k, i]] = cur_mean;
+                    }
+                }
+            }
+        }
+
+        let mut final_k = 1;
+        let mut max_score = f64::NEG_INFINITY;
+        for k in 1..=k_max {
+            if dp_score[[k, n - 1]] > max_score {
+                max_score = dp_score[[k, n - 1]];
+                final_k = k;
+            }
+        }
+        if max_score == f64::NEG_INFINITY {
+            return None;
+        }
+
+        let mut splits = Vec::new();
+        let mut curr_i = n - 1;
+        let mut k_ptr = final_k;
+        while k_ptr > 1 {
+            let split_pt = best_split[[k_ptr, curr_i]];
+            splits.push(split_pt);
+            curr_i = split_pt;
+            k_ptr -= 1;
+        }
+        splits.sort();
+
+        Some((max_score, splits))
+    }
+
+    fn reconstruct_bins(&self, stats: &PreContBinStats, splits: Vec<usize>) -> Vec<ContBin> {
+        let mut bins = Vec::new();
+        let n = stats.edges.len();
+        let mut start_idx = 0;
+        let mut all_splits = splits.clone();
+        all_splits.push(n - 1);
+
+        for (bin_id, &end_idx) in all_splits.iter().enumerate() {
+            let left = if start_idx == 0 {
+                f64::NEG_INFINITY
+            } else {
+                stats.edges[start_idx - 1]
+            };
+            let right = if end_idx == n - 1 {
+                f64::INFINITY
+            } else {
+                stats.edges[end_idx]
+            };
+
+            let summary = stats.summarize_range(start_idx, end_idx);
+            bins.push(ContBin::from_summary(
+                bin_id,
+                (left, right),
+                &summary,
+                false,
+            ));
+            start_idx = end_idx + 1;
+        }
+
+        if stats.missing.count > 0 {
+            bins.push(ContBin::from_summary(
+                bins.len(),
+                (f64::NAN, f64::NAN),
+                &stats.missing,
+                true,
+            ));
+        }
+        bins
+    }
+
+    pub fn execute_transform(x: ArrayView1<f64>, bins: &[ContBin]) -> Vec<f64> {
+        let total_count: i32 = bins.iter().map(|b| b.count).sum();
+        let total_sum: f64 = bins.iter().map(|b| b.sum).sum();
+        let grand_mean = if total_count > 0 {
+            total_sum / total_count as f64
+        } else {
+            0.0
+        };
+        let missing_mean = bins
+            .iter()
+            .find(|b| b.is_missing)
+            .map(|b| b.mean)
+            .unwrap_or(grand_mean);
+        let thresholds: Vec<f64> = bins
+            .iter()
+            .filter(|b| !b.is_missing)
+            .map(|b| b.range.1)
+            .collect();
+        let mean_map: Vec<f64> = bins
+            .iter()
+            .filter(|b| !b.is_missing)
+            .map(|b| b.mean)
+            .collect();
+
+        x.iter()
+            .map(|&val| {
+                if val.is_nan() {
+                    missing_mean
+                } else {
+                    let idx = thresholds
+                        .binary_search_by(|probe| {
+                            if probe < &val {
+                                std::cmp::Ordering::Less
+                            } else {
+                                std::cmp::Ordering::Greater
+                            }
+                        })
+                        .unwrap_err();
+                    mean_map[idx]
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{PenaltyFamily, Regularization};
+    use crate::testutil::Lcg;
+    use ndarray::Array1;
+
+    /// Scores in `[0, 100)` whose target steps from 0 to 1 at 30 and to 3 at
+    /// 70, plus uniform noise.
+    fn steps(n: usize) -> (Array1<f64>, Array1<f64>) {
+        let mut rng = Lcg::new(11);
+        let x: Vec<f64> = (0..n)
+            .map(|_| (rng.uniform() * 1000.0).floor() / 10.0)
+            .collect();
+        let y: Vec<f64> = x
+            .iter()
+            .map(|&v| {
+                let step = if v < 30.0 {
+                    0.0
+                } else if v < 70.0 {
+                    1.0
+                } else {
+                    3.0
+                };
+                step + 0.5 * rng.uniform()
+            })
+            .collect();
+        (Array1::from(x), Array1::from(y))
+    }
+
+    fn fit(config: ContinuousConfig, x: &Array1<f64>, y: &Array1<f64>) -> ContinuousModel {
+        ContinuousBinning::new(config)
+            .fit(x.view(), y.view())
+            .unwrap()
+    }
+
+    fn means(model: &ContinuousModel) -> Vec<f64> {
+        model.bins.iter().map(|b| b.mean).collect()
+    }
+
+    #[test]
+    fn penalty_keeps_the_dp_from_filling_max_bins() {
+        let (x, y) = steps(3000);
+        let config = ContinuousConfig::new(10, 0.02, 0.6)
+            .unwrap()
+            .with_monotonic_trend(MonotonicTrend::None)
+            .unwrap();
+        let unpenalized = config
+            .clone()
+            .with_regularization(Regularization {
+                family: PenaltyFamily::None,
+                strength: 0.0,
+                target_bin_pct: None,
+            })
+            .unwrap();
+        assert_eq!(fit(unpenalized, &x, &y).bins.len(), 10);
+
+        let model = fit(config, &x, &y);
+        assert!(model.bins.len() < 10, "{} bins", model.bins.len());
+        assert!(model.bins.len() >= 3, "{} bins", model.bins.len());
+    }
+
+    #[test]
+    fn trend_is_chosen_or_forced() {
+        let (x, y) = steps(3000);
+        let config = ContinuousConfig::new(6, 0.05, 1.0).unwrap();
+        let auto = means(&fit(config.clone(), &x, &y));
+        assert!(auto.len() > 1);
+        assert!(auto.windows(2).all(|w| w[1] >= w[0]), "{:?}", auto);
+
+        let descending = config
+            .with_monotonic_trend(MonotonicTrend::Descending)
+            .unwrap();
+        let forced = means(&fit(descending, &x, &y));
+        assert!(forced.windows(2).all(|w| w[1] <= w[0]), "{:?}", forced);
+    }
+
+    #[test]
+    fn transform_maps_to_bin_means() {
+        let (mut x, y) = steps(2000);
+        x[0] = f64::NAN;
+        let model = fit(ContinuousConfig::new(5, 0.05, 0.6).unwrap(), &x, &y);
+        let missing = model.bins.last().unwrap();
+        assert!(missing.is_missing);
+        assert_eq!(missing.count, 1);
+
+        let output = model.transform(x.view());
+        assert_eq!(output[0], missing.mean);
+        for (&v, &o) in x.iter().zip(output.iter()).skip(1) {
+            let bin = model
+                .bins
+                .iter()
+                .find(|b| !b.is_missing && b.range.0 < v && v <= b.range.1)
+                .unwrap();
+            assert_eq!(o, bin.mean);
+        }
+    }
+
+    #[test]
+    fn infeasible_size_bounds_are_an_error() {
+        let (x, y) = steps(1000);
+        let config = ContinuousConfig::new(2, 0.05, 0.3).unwrap();
+        assert!(matches!(
+            ContinuousBinning::new(config).fit(x.view(), y.view()),
+            Err(BinningError::InvalidInput(_))
+        ));
+    }
+}
@@ -0,0 +1,201 @@
+use crate::config::{EventLimits, Separation, Significance};
+use crate::error::BinningError;
+use crate::numerical::Trend;
+use crate::significance;
+use std::fmt;
+
+/// A constraint broken by a manually edited binning.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation {
+    /// Bin `bin_id` holds `pct` of the rows, below `min_bin_pct`.
+    TooSmall { bin_id: usize, pct: f64 },
+    /// Bin `bin_id` holds `pct` of the rows, above `max_bin_pct`.
+    TooLarge { bin_id: usize, pct: f64 },
+    /// Bin `bin_id` holds `pos` events and `neg` non-events, outside the
+    /// event count limits.
+    EventCount { bin_id: usize, pos: i32, neg: i32 },
+    /// Bin `bin_id` does not differ significantly from the bin before it.
+    Insignificant { bin_id: usize, pvalue: f64 },
+    /// The WoE or event rate of bin `bin_id` is too close to the bin before it.
+    TooClose { bin_id: usize },
+    /// More regular bins than `max_bins`.
+    TooManyBins { n_bins: usize, max_bins: usize },
+    /// The WoE of bin `bin_id` breaks `trend` relative to the bin before it.
+    Trend { bin_id: usize, trend: Trend },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::TooSmall { bin_id, pct } => {
+                write!(
+                    f,
+                    "Bin {} holds {:.4} of the rows, below min_bin_pct",
+                    bin_id, pct
+                )
+            }
+            Violation::TooLarge { bin_id, pct } => {
+                write!(
+                    f,
+                    "Bin {} holds {:.4} of the rows, above max_bin_pct",
+                    bin_id, pct
+                )
+            }
+            Violation::EventCount { bin_id, pos, neg } => {
+                write!(
+                    f,
+                    "Bin {} holds {} events and {} non-events, outside the event count limits",
+                    bin_id, pos, neg
+                )
+            }
+            Violation::Insignificant { bin_id, pvalue } => {
+                write!(
+                    f,
+                    "Bin {} differs from the previous bin with p-value {:.4}, above max_pvalue",
+                    bin_id, pvalue
+                )
+            }
+            Violation::TooClose { bin_id } => {
+                write!(
+                    f,
+                    "Bin {} is closer to the previous bin than min_woe_diff or \
+                     min_event_rate_diff allow",
+                    bin_id
+                )
+            }
+            Violation::TooManyBins { n_bins, max_bins } => {
+                write!(f, "{} bins exceed max_bins ({})", n_bins, max_bins)
+            }
+            Violation::Trend { bin_id, trend } => {
+                write!(
+                    f,
+                    "The WoE of bin {} breaks the '{}' trend",
+                    bin_id,
+                    trend.as_str()
+                )
+            }
+        }
+    }
+}
+
+/// The constraints the solver enforces, for re-checking edited bins.
+pub(crate) struct Constraints {
+    pub max_bins: usize,
+    pub min_bin_pct: f64,
+    pub max_bin_pct: f64,
+    pub event_limits: EventLimits,
+    pub significance: Option<Significance>,
+    pub separation: Separation,
+    pub trend: Option<Trend>,
+}
+
+/// What the constraints look at in one regular bin.
+pub(crate) struct Checked {
+    pub bin_id: usize,
+    pub woe: f64,
+    pub event_rate: f64,
+    pub weight: f64,
+    pub pos: i32,
+    pub neg: i32,
+}
+
+impl Constraints {
+    /// Checks the regular bins, in order. Shares are taken over the regular
+    /// bins, as in the DP.
+    pub fn check(&self, bins: &[Checked]) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        if bins.len() > self.max_bins {
+            violations.push(Violation::TooManyBins {
+                n_bins: bins.len(),
+                max_bins: self.max_bins,
+            });
+        }
+
+        let total: f64 = bins.iter().map(|b| b.weight).sum();
+        let min_samples = (total * self.min_bin_pct).floor();
+        let max_samples = (total * self.max_bin_pct).floor();
+        for bin in bins {
+            let (bin_id, pct) = (bin.bin_id, bin.weight / total);
+            if bin.weight < min_samples {
+                violations.push(Violation::TooSmall { bin_id, pct });
+            } else if bin.weight > max_samples {
+                violations.push(Violation::TooLarge { bin_id, pct });
+            }
+            if !self.event_limits.allows(bin.pos, bin.neg) {
+                violations.push(Violation::EventCount {
+                    bin_id,
+                    pos: bin.pos,
+                    neg: bin.neg,
+                });
+            }
+        }
+
+        for pair in bins.windows(2) {
+            let (prev, cur) = (&pair[0], &pair[1]);
+            if let Some(significance) = self.significance {
+                let pvalue = significance::pvalue(
+                    significance.test,
+                    (prev.pos, prev.neg),
+                    (cur.pos, cur.neg),
+                );
+                if pvalue > significance.max_pvalue {
+                    violations.push(Violation::Insignificant {
+                        bin_id: cur.bin_id,
+                        pvalue,
+                    });
+                }
+            }
+            if !self
+                .separation
+                .separates((prev.woe, prev.event_rate), (cur.woe, cur.event_rate))
+            {
+                violations.push(Violation::TooClose { bin_id: cur.bin_id });
+            }
+        }
+
+        // Peak and valley turn at the first step that only the next phase allows.
+        if let Some(trend) = self.trend {
+            let mut phase = 0;
+            for pair in bins.windows(2) {
+                let (prev, cur, bin_id) = (pair[0].woe, pair[1].woe, pair[1].bin_id);
+                if trend.allows(phase, phase, prev, cur) {
+                    continue;
+                }
+                if phase + 1 < trend.n_phases() && trend.allows(phase, phase + 1, prev, cur) {
+                    phase += 1;
+                } else {
+                    violations.push(Violation::Trend { bin_id, trend });
+                }
+            }
+        }
+        violations
+    }
+}
+
+pub(crate) fn no_prebins() -> BinningError {
+    BinningError::InvalidInput(
+        "Editing needs the prebinning statistics, which this model was saved without; refit it"
+            .to_string(),
+    )
+}
+
+pub(crate) fn check_regular(bin_id: usize, n_regular: usize) -> Result<(), BinningError> {
+    if bin_id >= n_regular {
+        return Err(BinningError::InvalidInput(format!(
+            "Bin {} is not one of the {} regular bins",
+            bin_id, n_regular
+        )));
+    }
+    Ok(())
+}
+
+pub(crate) fn check_mergeable(bin_id: usize, n_regular: usize) -> Result<(), BinningError> {
+    check_regular(bin_id, n_regular)?;
+    if bin_id + 1 == n_regular {
+        return Err(BinningError::InvalidInput(format!(
+            "Bin {} is the last regular bin and has no next bin to merge with",
+            bin_id
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BinningError {
+    InvalidConfig(String),
+    InvalidInput(String),
+    LengthMismatch {
+        name: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    EmptyInput,
+    AllMissing,
+    InfiniteValue,
+    NonBinaryTarget(i32),
+    NoEvents,
+    NoNonEvents,
+    InvalidWeight(String),
+    UnseenCategory(i32),
+    Serialization(String),
+    Variable {
+        index: usize,
+        source: Box<BinningError>,
+    },
+}
+
+impl fmt::Display for BinningError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinningError::InvalidConfig(msg) => write!(f, "{}", msg),
+            BinningError::InvalidInput(msg) => write!(f, "{}", msg),
+            BinningError::LengthMismatch {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Length mismatch: x has {} rows but {} has {}",
+                expected, name, found
+            ),
+            BinningError::EmptyInput => write!(f, "Input x is empty"),
+            BinningError::AllMissing => write!(f, "Every value of x is missing"),
+            BinningError::InfiniteValue => write!(f, "Input contains infinite values"),
+            BinningError::NonBinaryTarget(v) => {
+                write!(f, "Target y must be binary (0 or 1), found {}", v)
+            }
+            BinningError::NoEvents => {
+                write!(f, "Target y has no positives (1) among non-missing values")
+            }
+            BinningError::NoNonEvents => {
+                write!(f, "Target y has no negatives (0) among non-missing values")
+            }
+            BinningError::InvalidWeight(msg) => write!(f, "Invalid sample_weight: {}", msg),
+            BinningError::UnseenCategory(v) => {
+                write!(f, "Category {} was not seen during fit", v)
+            }
+            BinningError::Serialization(msg) => write!(f, "Serialization error: {}", msg),
+            BinningError::Variable { index, source } => {
+                write!(f, "Variable {}: {}", index, source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BinningError {}
@@ -0,0 +1,153 @@
+use crate::config::Significance;
+use crate::significance;
+use crate::woeiv::calc_woe_iv;
+use serde::{Deserialize, Serialize};
+
+/// A fitted bin table recomputed on new data, bin for bin.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Evaluation<B> {
+    /// The fitted bins with counts, WoE and IV taken from the new data.
+    pub bins: Vec<B>,
+    /// Per bin, how the new WoE departs from the fitted one.
+    pub flips: Vec<Flip>,
+    /// Total IV on the new data.
+    pub iv: f64,
+    /// New rows that fall in no fitted bin: unseen categories, or missing
+    /// values when the fit saw none.
+    pub n_unbinned: usize,
+}
+
+impl<B> Evaluation<B> {
+    /// Whether the regular bins keep the fitted WoE order.
+    pub fn order_holds(&self) -> bool {
+        self.flips.iter().all(|f| !f.order)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Flip {
+    /// The WoE changed sign.
+    pub sign: bool,
+    /// The WoE step from the previous regular bin reversed direction.
+    pub order: bool,
+}
+
+/// A fitted bin that can be recounted on new data.
+pub(crate) trait Recount: Clone {
+    /// Neither the missing bin nor a special one.
+    fn is_regular(&self) -> bool;
+    fn woe(&self) -> f64;
+    fn iv(&self) -> f64;
+    /// Unweighted `(pos, neg)` counts.
+    fn counts(&self) -> (i32, i32);
+    /// Replaces the fitted counts, weights, WoE and IV with those of the new
+    /// data.
+    fn recount(&mut self, pos: i32, neg: i32, woe: f64, iv: f64);
+    fn set_pvalue(&mut self, pvalue: f64);
+}
+
+/// Recomputes the `fitted` bins on the new rows, given as the position of the
+/// bin each falls in and its target. With a `significance` limit, each
+/// regular bin gets the p-value against the bin before it.
+pub(crate) fn evaluate<B: Recount>(
+    fitted: &[B],
+    rows: impl Iterator<Item = (Option<usize>, i32)>,
+    significance: Option<Significance>,
+) -> Evaluation<B> {
+    let (counts, n_unbinned) = tally(fitted.len(), rows);
+    let total_pos = counts.iter().map(|c| c.0).sum::<i32>() as f64;
+    let total_neg = counts.iter().map(|c| c.1).sum::<i32>() as f64;
+    let regular: Vec<bool> = fitted.iter().map(|b| b.is_regular()).collect();
+
+    let mut bins = fitted.to_vec();
+    for (bin, &(pos, neg)) in bins.iter_mut().zip(&counts) {
+        let (woe, iv) = calc_woe_iv(pos as f64, neg as f64, total_pos, total_neg);
+        bin.recount(pos, neg, woe, iv);
+    }
+    if let Some(significance) = significance {
+        for b in 1..bins.len() {
+            if regular[b] {
+                let pvalue =
+                    significance::pvalue(significance.test, bins[b - 1].counts(), bins[b].counts());
+                bins[b].set_pvalue(pvalue);
+            }
+        }
+    }
+
+    let fitted: Vec<f64> = fitted.iter().map(|b| b.woe()).collect();
+    let new: Vec<f64> = bins.iter().map(|b| b.woe()).collect();
+    Evaluation {
+        flips: flips(&fitted, &new, &regular),
+        iv: bins.iter().map(|b| b.iv()).sum(),
+        bins,
+        n_unbinned,
+    }
+}
+
+/// `(pos, neg)` counts per bin of the new rows, given as the position of the
+/// bin each falls in and its target, and the number of unbinned rows.
+fn tally(
+    n_bins: usize,
+    rows: impl Iterator<Item = (Option<usize>, i32)>,
+) -> (Vec<(i32, i32)>, usize) {
+    let mut counts = vec![(0, 0); n_bins];
+    let mut n_unbinned = 0;
+    for (position, target) in rows {
+        match position {
+            Some(b) if target == 1 => counts[b].0 += 1,
+            Some(b) => counts[b].1 += 1,
+            None => n_unbinned += 1,
+        }
+    }
+    (counts, n_unbinned)
+}
+
+/// Compares the new WoE of each bin with the fitted one. Order is only
+/// compared between consecutive bins flagged `regular`.
+fn flips(fitted: &[f64], new: &[f64], regular: &[bool]) -> Vec<Flip> {
+    let mut prev: Option<usize> = None;
+    (0..fitted.len())
+        .map(|b| {
+            let sign = fitted[b] * new[b] < 0.0;
+            let mut order = false;
+            if regular[b] {
+                if let Some(p) = prev {
+                    let (step, new_step) = (fitted[b] - fitted[p], new[b] - new[p]);
+                    order = step * new_step < 0.0;
+                }
+                prev = Some(b);
+            }
+            Flip { sign, order }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::NumericalConfig;
+    use crate::numerical::NumericalBinning;
+    use crate::testutil::scores;
+
+    #[test]
+    fn swapped_targets_flip_every_bin() {
+        let (x, y) = scores(3000, 6);
+        let model = NumericalBinning::new(NumericalConfig::new(5, 0.05, 0.4).unwrap())
+            .fit(x.view(), y.view())
+            .unwrap();
+        assert!(model.bins.len() > 2);
+
+        let same = model.evaluate(x.view(), y.view()).unwrap();
+        assert!(same.order_holds());
+        assert!(same.flips.iter().all(|f| !f.sign));
+        assert!((same.iv - model.total_iv()).abs() < 1e-9);
+
+        let swapped = y.mapv(|v| 1 - v);
+        let evaluation = model.evaluate(x.view(), swapped.view()).unwrap();
+        assert_eq!(evaluation.n_unbinned, 0);
+        assert!(evaluation.flips[0].sign && !evaluation.flips[0].order);
+        assert!(evaluation.flips[1..].iter().all(|f| f.sign && f.order));
+        for (fitted, new) in model.bins.iter().zip(&evaluation.bins) {
+            assert_eq!((fitted.pos, fitted.neg), (new.neg, new.pos));
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::error::BinningError;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct EnvelopeRef<'a, T> {
+    schema_version: u32,
+    kind: &'a str,
+    #[serde(flatten)]
+    payload: &'a T,
+}
+
+#[derive(Deserialize)]
+struct Envelope<T> {
+    schema_version: u32,
+    kind: String,
+    #[serde(flatten)]
+    payload: T,
+}
+
+pub(crate) fn to_json<T: Serialize>(kind: &str, payload: &T) -> Result<String, BinningError> {
+    let envelope = EnvelopeRef {
+        schema_version: SCHEMA_VERSION,
+        kind,
+        payload,
+    };
+    serde_json::to_string(&envelope).map_err(|e| BinningError::Serialization(e.to_string()))
+}
+
+pub(crate) fn from_json<T: DeserializeOwned>(kind: &str, json: &str) -> Result<T, BinningError> {
+    let envelope: Envelope<T> =
+        serde_json::from_str(json).map_err(|e| BinningError::Serialization(e.to_string()))?;
+    if envelope.schema_version > SCHEMA_VERSION {
+        return Err(BinningError::Serialization(format!(
+            "Unsupported schema_version {} (latest supported is {})",
+            envelope.schema_version, SCHEMA_VERSION
+        )));
+    }
+    if envelope.kind != kind {
+        return Err(BinningError::Serialization(format!(
+            "Expected a '{}' binning, found '{}'",
+            kind, envelope.kind
+        )));
+    }
+    Ok(envelope.payload)
+}
+
+/// JSON has no representation for NaN or infinities, so non-finite values
+/// are written as the strings "NaN", "inf" and "-inf".
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum JsonFloat {
+    Num(f64),
+    Str(String),
+}
+
+impl JsonFloat {
+    fn from_f64(v: f64) -> Self {
+        if v.is_nan() {
+            JsonFloat::Str("NaN".to_string())
+        } else if v == f64::INFINITY {
+            JsonFloat::Str("inf".to_string())
+        } else if v == f64::NEG_INFINITY {
+            JsonFloat::Str("-inf".to_string())
+        } else {
+            JsonFloat::Num(v)
+        }
+    }
+
+    fn into_f64<E: serde::de::Error>(self) -> Result<f64, E> {
+        match self {
+            JsonFloat::Num(v) => Ok(v),
+            JsonFloat::Str(s) => match s.as_str() {
+                "NaN" => Ok(f64::NAN),
+                "inf" => Ok(f64::INFINITY),
+                "-inf" => Ok(f64::NEG_INFINITY),
+                other => Err(E::custom(format!("invalid float '{}'", other))),
+            },
+        }
+    }
+}
+
+pub(crate) mod float_pair {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &(f64, f64), s: S) -> Result<S::Ok, S::Error> {
+        (JsonFloat::from_f64(value.0), JsonFloat::from_f64(value.1)).serialize(s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<(f64, f64), D::Error> {
+        let (left, right) = <(JsonFloat, JsonFloat)>::deserialize(d)?;
+        Ok((left.into_f64()?, right.into_f64()?))
+    }
+}
+
+pub(crate) mod float {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &f64, s: S) -> Result<S::Ok, S::Error> {
+        JsonFloat::from_f64(*value).serialize(s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<f64, D::Error> {
+        JsonFloat::deserialize(d)?.into_f64()
+    }
+}
+
+pub(crate) mod float_vec {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &[f64], s: S) -> Result<S::Ok, S::Error> {
+        s.collect_seq(value.iter().map(|&v| JsonFloat::from_f64(v)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<f64>, D::Error> {
+        Vec::<JsonFloat>::deserialize(d)?
+            .into_iter()
+            .map(JsonFloat::into_f64)
+            .collect()
+    }
+}
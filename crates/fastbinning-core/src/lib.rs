@@ -0,0 +1,47 @@
+pub mod categorical;
+pub mod config;
+pub mod continuous;
+pub mod edit;
+pub mod error;
+pub mod evaluate;
+pub mod json;
+pub mod multiclass;
+pub mod numerical;
+pub mod prebinning;
+pub mod precategorical;
+pub mod precontinuous;
+pub mod premulticlass;
+pub mod prenumerical;
+pub mod process;
+pub mod significance;
+pub mod special;
+pub mod stability;
+#[cfg(test)]
+mod testutil;
+pub mod validate;
+pub mod weights;
+pub mod woeiv;
+
+pub use crate::categorical::{CatBin, CategoricalBinning, CategoricalModel};
+pub use crate::config::{
+    CategoricalConfig, ContinuousConfig, EventLimits, MonotonicTrend, MulticlassConfig,
+    NumericalConfig, PenaltyFamily, PrebinMethod, Prebinning, Regularization, Separation,
+    Significance, Solver, UnseenPolicy, UserSplits,
+};
+pub use crate::continuous::{ContBin, ContinuousBinning, ContinuousModel};
+pub use crate::edit::Violation;
+pub use crate::error::BinningError;
+pub use crate::evaluate::{Evaluation, Flip};
+pub use crate::multiclass::{MultiBin, MulticlassBinning, MulticlassModel};
+pub use crate::numerical::{
+    FitScore, NumBin, NumericalBinning, NumericalModel, OptimalityGap, Trend,
+};
+pub use crate::process::{
+    BinningProcess, BinningProcessModel, FittedVariable, VariableConfig, VariableKind,
+    VariableSummary,
+};
+pub use crate::significance::PValueTest;
+pub use crate::special::SpecialGroup;
+pub use crate::stability::{BinDrift, Stability, StabilityBin};
+pub use crate::weights::BinWeights;
+pub use crate::woeiv::Divergence;
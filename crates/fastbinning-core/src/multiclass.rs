@@ -0,0 +1,394 @@
+use crate::config::MulticlassConfig;
+use crate::error::BinningError;
+use crate::json;
+use crate::prebinning::prebin_ends;
+use crate::premulticlass::{PreMultiBinStats, calc_one_vs_rest};
+use crate::validate;
+use ndarray::{Array2, ArrayView1};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiBin {
+    pub bin_id: usize,
+    #[serde(with = "json::float_pair")]
+    pub range: (f64, f64),
+    pub counts: Vec<i32>,
+    #[serde(with = "json::float_vec")]
+    pub woe: Vec<f64>,
+    #[serde(with = "json::float")]
+    pub iv: f64,
+    pub is_missing: bool,
+}
+
+impl MultiBin {
+    #[inline]
+    pub fn count(&self) -> i32 {
+        self.counts.iter().sum()
+    }
+
+    pub fn event_rates(&self) -> Vec<f64> {
+        let count = self.count();
+        self.counts
+            .iter()
+            .map(|&c| {
+                if count > 0 {
+                    c as f64 / count as f64
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MulticlassModel {
+    pub config: MulticlassConfig,
+    pub classes: Vec<i32>,
+    pub bins: Vec<MultiBin>,
+}
+
+impl MulticlassModel {
+    pub fn transform(&self, x: ArrayView1<f64>) -> Array2<f64> {
+        MulticlassBinning::execute_transform(x, &self.bins, self.classes.len())
+    }
+
+    pub fn total_iv(&self) -> f64 {
+        self.bins.iter().map(|b| b.iv).sum()
+    }
+
+    pub fn to_json(&self) -> Result<String, BinningError> {
+        json::to_json("multiclass", self)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, BinningError> {
+        let model: Self = json::from_json("multiclass", json)?;
+        model.config.validate()?;
+        Ok(model)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MulticlassBinning {
+    pub config: MulticlassConfig,
+}
+
+impl MulticlassBinning {
+    pub fn new(config: MulticlassConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn fit(
+        &self,
+        x: ArrayView1<f64>,
+        y: ArrayView1<i32>,
+    ) -> Result<MulticlassModel, BinningError> {
+        let (classes, bins) = self.execute_fit(x, y)?;
+        Ok(MulticlassModel {
+            config: self.config.clone(),
+            classes,
+            bins,
+        })
+    }
+
+    pub fn execute_fit(
+        &self,
+        x: ArrayView1<f64>,
+        y: ArrayView1<i32>,
+    ) -> Result<(Vec<i32>, Vec<MultiBin>), BinningError> {
+        validate::check_length("y", x.len(), y.len())?;
+        validate::check_numerical(x.iter())?;
+
+        let (classes, stats) = self.prebinning(x, y)?;
+        if classes.len() < 2 {
+            return Err(BinningError::InvalidInput(
+                "Target y must contain at least two classes".to_string(),
+            ));
+        }
+        let split_indices = self.split(&stats).ok_or_else(|| {
+            BinningError::InvalidInput(
+                "No binning satisfies min_bin_pct and max_bin_pct".to_string(),
+            )
+        })?;
+        Ok((classes, self.reconstruct_bins(&stats, split_indices)))
+    }
+
+    /// The classes are those of the rows with a value of x: a class seen only
+    /// where x is missing has no regular bin to be scored against.
+    fn prebinning(
+        &self,
+        x: ArrayView1<f64>,
+        y: ArrayView1<i32>,
+    ) -> Result<(Vec<i32>, PreMultiBinStats), BinningError> {
+        let mut classes: Vec<i32> = x
+            .iter()
+            .zip(y.iter())
+            .filter(|(v, _)| !v.is_nan())
+            .map(|(_, &t)| t)
+            .collect();
+        classes.par_sort_unstable();
+        classes.dedup();
+        let n_classes = classes.len();
+        let class_of = |t: i32| {
+            classes.binary_search(&t).map_err(|_| {
+                BinningError::InvalidInput(format!(
+                    "Class {} of y appears only in rows where x is missing",
+                    t
+                ))
+            })
+        };
+
+        let mut missing = vec![0; n_classes];
+        let mut data: Vec<(f64, usize)> = Vec::with_capacity(x.len());
+        for (&v, &t) in x.iter().zip(y.iter()) {
+            if v.is_nan() {
+                missing[class_of(t)?] += 1;
+                continue;
+            }
+            data.push((v, class_of(t)?));
+        }
+
+        data.par_sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut counts: Vec<i32> = Vec::new();
+        let mut edges: Vec<f64> = Vec::new();
+        let mut curr = vec![0; n_classes];
+        let mut start = 0;
+        for end in prebin_ends(&self.config.prebinning, &data) {
+            curr.iter_mut().for_each(|c| *c = 0);
+            data[start..=end]
+                .iter()
+                .for_each(|&(_, class)| curr[class] += 1);
+            counts.extend_from_slice(&curr);
+            edges.push(data[end].0);
+            start = end + 1;
+        }
+        let stats = PreMultiBinStats::new(&counts, n_classes, edges, missing);
+        Ok((classes, stats))
+    }
+
+    /// Splits of the best binning with the most bins, or `None` when no
+    /// binning satisfies the size bounds.
+    fn split(&self, stats: &PreMultiBinStats) -> Option<Vec<usize>> {
+        let n = stats.edges.len();
+        let k_max = self.config.max_bins.min(n);
+        let total_samples: i32 = stats.totals.iter().sum();
+        let min_samples = (total_samples as f64 * self.config.min_bin_pct) as i32;
+        let max_samples = (total_samples as f64 * self.config.max_bin_pct) as i32;
+        let mut dp = Array2::<f64>::from_elem((k_max + 1, n), f64::NEG_INFINITY);
+        let mut best_split = Array2::<usize>::from_elem((k_max + 1, n), 0);
+        let mut buf = vec![0; stats.n_classes];
+
+        for i in 0..n {
+            let total = stats.get_count(0, i);
+            if total >= min_samples && total <= max_samples {
+                dp[[1, i]] = stats.calc_divergence_range(0, i, &mut buf);
+            }
+        }
+
+        for k in 2..=k_max {
+            for i in (k - 1)..n {
+                for j in (k - 2)..i {
+                    if dp[[k - 1, j]] == f64::NEG_INFINITY {
+                        continue;
+                    }
+                    let cur_count = stats.get_count(j + 1, i);
+                    if cur_count < min_samples || cur_count > max_samples {
+                        continue;
+                    }
+                    let current = dp[[k - 1, j]] + stats.calc_divergence_range(j + 1, i, &mut buf);
+                    if current > dp[[k, i]] {
+                        dp[[k, i]] = current;
+                        best_split[[k, i]] = j;
+                    }
+                }
+            }
+        }
+
+        let final_k = (1..=k_max)
+            .rev()
+            .find(|&k| dp[[k, n - 1]] != f64::NEG_INFINITY)?;
+
+        let mut splits = Vec::new();
+        let mut curr_i = n - 1;
+        let mut k_ptr = final_k;
+        while k_ptr > 1 {
+            let split_pt = best_split[[k_ptr, curr_i]];
+            splits.push(split_pt);
+            curr_i = split_pt;
+            k_ptr -= 1;
+        }
+        splits.sort();
+        Some(splits)
+    }
+
+    fn reconstruct_bins(&self, stats: &PreMultiBinStats, splits: Vec<usize>) -> Vec<MultiBin> {
+        let grand_totals: Vec<i32> = stats
+            .totals
+            .iter()
+            .zip(stats.missing.iter())
+            .map(|(t, m)| t + m)
+            .collect();
+
+        let mut bins = Vec::new();
+        let n = stats.edges.len();
+        let mut start_idx = 0;
+        let mut all_splits = splits.clone();
+        all_splits.push(n - 1);
+
+        for (bin_id, &end_idx) in all_splits.iter().enumerate() {
+            let counts = stats.get_counts(start_idx, end_idx);
+            let left = if start_idx == 0 {
+                f64::NEG_INFINITY
+            } else {
+                stats.edges[start_idx - 1]
+            };
+            let right = if end_idx == n - 1 {
+                f64::INFINITY
+            } else {
+                stats.edges[end_idx]
+            };
+
+            let woe_iv = calc_one_vs_rest(&counts, &grand_totals);
+            bins.push(MultiBin {
+                bin_id,
+                range: (left, right),
+                counts,
+                woe: woe_iv.iter().map(|&(woe, _)| woe).collect(),
+                iv: woe_iv.iter().map(|&(_, iv)| iv).sum(),
+                is_missing: false,
+            });
+            start_idx = end_idx + 1;
+        }
+
+        if stats.missing.iter().sum::<i32>() > 0 {
+            let woe_iv = calc_one_vs_rest(&stats.missing, &grand_totals);
+            bins.push(MultiBin {
+                bin_id: bins.len(),
+                range: (f64::NAN, f64::NAN),
+                counts: stats.missing.clone(),
+                woe: woe_iv.iter().map(|&(woe, _)| woe).collect(),
+                iv: woe_iv.iter().map(|&(_, iv)| iv).sum(),
+                is_missing: true,
+            });
+        }
+        bins
+    }
+
+    pub fn execute_transform(
+        x: ArrayView1<f64>,
+        bins: &[MultiBin],
+        n_classes: usize,
+    ) -> Array2<f64> {
+        let mut output = Array2::<f64>::zeros((x.len(), n_classes));
+        let missing_woe = bins.iter().find(|b| b.is_missing).map(|b| &b.woe);
+        let thresholds: Vec<f64> = bins
+            .iter()
+            .filter(|b| !b.is_missing)
+            .map(|b| b.range.1)
+            .collect();
+        let woe_map: Vec<&Vec<f64>> = bins
+            .iter()
+            .filter(|b| !b.is_missing)
+            .map(|b| &b.woe)
+            .collect();
+
+        for (mut row, &val) in output.rows_mut().into_iter().zip(x.iter()) {
+            let woe = if val.is_nan() {
+                missing_woe
+            } else {
+                let idx = thresholds
+                    .binary_search_by(|probe| {
+                        if probe < &val {
+                            std::cmp::Ordering::Less
+                        } else {
+                            std::cmp::Ordering::Greater
+                        }
+                    })
+                    .unwrap_err();
+                Some(woe_map[idx])
+            };
+            if let Some(woe) = woe {
+                row.iter_mut().zip(woe.iter()).for_each(|(o, &w)| *o = w);
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{PrebinMethod, Prebinning};
+    use crate::testutil::Lcg;
+    use ndarray::Array1;
+
+    /// Scores in `[0, 100)` whose class, 0 to 2, tends to grow with x.
+    fn sample(n: usize) -> (Vec<f64>, Vec<i32>) {
+        let mut rng = Lcg::new(7);
+        let x: Vec<f64> = (0..n)
+            .map(|_| (rng.uniform() * 1000.0).floor() / 10.0)
+            .collect();
+        let y = x
+            .iter()
+            .map(|&v| ((v / 50.0 + rng.uniform() * 1.5) as i32).min(2))
+            .collect();
+        (x, y)
+    }
+
+    fn fit(
+        config: MulticlassConfig,
+        x: Vec<f64>,
+        y: Vec<i32>,
+    ) -> Result<MulticlassModel, BinningError> {
+        MulticlassBinning::new(config).fit(Array1::from(x).view(), Array1::from(y).view())
+    }
+
+    #[test]
+    fn class_seen_only_where_x_is_missing_is_rejected() {
+        let (mut x, mut y) = sample(1000);
+        y.iter_mut().for_each(|t| *t = (*t).min(1));
+        x.extend([f64::NAN; 10]);
+        y.extend([2; 10]);
+        let config = MulticlassConfig::new(5, 0.05, 0.5).unwrap();
+        assert!(matches!(
+            fit(config, x, y),
+            Err(BinningError::InvalidInput(msg)) if msg.contains("Class 2")
+        ));
+    }
+
+    #[test]
+    fn bins_account_for_every_row() {
+        let (mut x, y) = sample(2000);
+        for v in x.iter_mut().step_by(50) {
+            *v = f64::NAN;
+        }
+        let prebinning = Prebinning {
+            method: PrebinMethod::Uniform,
+            n_bins: Some(40),
+        };
+        let config = MulticlassConfig::new(5, 0.05, 0.5)
+            .unwrap()
+            .with_prebinning(prebinning)
+            .unwrap();
+        let model = fit(config, x, y.clone()).unwrap();
+        assert_eq!(model.classes, vec![0, 1, 2]);
+        assert!(model.bins.last().unwrap().is_missing);
+        assert!(model.total_iv().is_finite());
+        for (c, &class) in model.classes.iter().enumerate() {
+            let total: i32 = model.bins.iter().map(|b| b.counts[c]).sum();
+            assert_eq!(total as usize, y.iter().filter(|&&t| t == class).count());
+        }
+    }
+
+    #[test]
+    fn infeasible_size_bounds_are_an_error() {
+        let (x, y) = sample(1000);
+        let config = MulticlassConfig::new(2, 0.05, 0.3).unwrap();
+        assert!(matches!(
+            fit(config, x, y),
+            Err(BinningError::InvalidInput(_))
+        ));
+    }
+}
@@ -0,0 +1,1849 @@
+use crate::config::{MonotonicTrend, NumericalConfig, Solver, UserSplits};
+use crate::edit::{self, Checked, Constraints, Violation};
+use crate::error::BinningError;
+use crate::evaluate::{self, Evaluation, Recount};
+use crate::json;
+use crate::prebinning::{self, prebin_ends};
+use crate::prenumerical::PreNumBinStats;
+use crate::significance;
+use crate::special::{SpecialGroup, SpecialLookup, SpecialTally};
+use crate::stability::{self, Fitted, Stability};
+use crate::validate;
+use crate::weights::BinWeights;
+use crate::woeiv::{calc_event_rate, calc_woe_iv};
+use ndarray::{Array3, Array4, ArrayView1};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Trend {
+    #[serde(rename = "ascending")]
+    Increasing,
+    #[serde(rename = "descending")]
+    Decreasing,
+    /// Increasing up to a turning point, decreasing after it.
+    #[serde(rename = "peak")]
+    Peak,
+    /// Decreasing up to a turning point, increasing after it.
+    #[serde(rename = "valley")]
+    Valley,
+    #[serde(rename = "none")]
+    Unconstrained,
+}
+
+impl Trend {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Trend::Increasing => "ascending",
+            Trend::Decreasing => "descending",
+            Trend::Peak => "peak",
+            Trend::Valley => "valley",
+            Trend::Unconstrained => "none",
+        }
+    }
+
+    /// Number of DP phases: unimodal trends are split at their turning point.
+    #[inline]
+    pub(crate) fn n_phases(&self) -> usize {
+        match self {
+            Trend::Increasing | Trend::Decreasing | Trend::Unconstrained => 1,
+            Trend::Peak | Trend::Valley => 2,
+        }
+    }
+
+    /// Direction the next bin must move in to go from phase `from` to phase
+    /// `to`, or `None` if that phase change is not allowed.
+    #[inline]
+    pub(crate) fn step(&self, from: usize, to: usize) -> Option<Step> {
+        match (self, from, to) {
+            (Trend::Increasing, 0, 0) => Some(Step::Up),
+            (Trend::Decreasing, 0, 0) => Some(Step::Down),
+            (Trend::Unconstrained, 0, 0) => Some(Step::Any),
+            (Trend::Peak, 0, 0) | (Trend::Valley, 0, 1) | (Trend::Valley, 1, 1) => Some(Step::Up),
+            (Trend::Valley, 0, 0) | (Trend::Peak, 0, 1) | (Trend::Peak, 1, 1) => Some(Step::Down),
+            _ => None,
+        }
+    }
+
+    /// Whether a bin scoring `cur` may follow one scoring `prev`, moving from
+    /// phase `from` to phase `to`.
+    #[inline]
+    pub(crate) fn allows(&self, from: usize, to: usize, prev: f64, cur: f64) -> bool {
+        match self.step(from, to) {
+            Some(Step::Up) => cur >= prev - f64::EPSILON,
+            Some(Step::Down) => cur <= prev + f64::EPSILON,
+            Some(Step::Any) => true,
+            None => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Step {
+    Up,
+    Down,
+    Any,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NumBin {
+    pub bin_id: usize,
+    #[serde(with = "json::float_pair")]
+    pub range: (f64, f64),
+    pub pos: i32,
+    pub neg: i32,
+    #[serde(with = "json::float")]
+    pub woe: f64,
+    #[serde(with = "json::float")]
+    pub iv: f64,
+    pub is_missing: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub special: Option<SpecialGroup>,
+    /// P-value of the significance test against the previous regular bin,
+    /// set when the config has a `significance` limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pvalue: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weights: Option<BinWeights>,
+}
+
+impl NumBin {
+    #[inline]
+    pub fn count(&self) -> i32 {
+        self.pos + self.neg
+    }
+
+    #[inline]
+    pub fn is_special(&self) -> bool {
+        self.special.is_some()
+    }
+
+    #[inline]
+    pub fn pos_weight(&self) -> f64 {
+        self.weights.map_or(self.pos as f64, |w| w.pos)
+    }
+
+    #[inline]
+    pub fn neg_weight(&self) -> f64 {
+        self.weights.map_or(self.neg as f64, |w| w.neg)
+    }
+
+    #[inline]
+    pub fn total_weight(&self) -> f64 {
+        self.pos_weight() + self.neg_weight()
+    }
+
+    #[inline]
+    pub fn event_rate(&self) -> f64 {
+        let total = self.total_weight();
+        if total > 0.0 {
+            self.pos_weight() / total
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Recount for NumBin {
+    #[inline]
+    fn is_regular(&self) -> bool {
+        !self.is_missing && !self.is_special()
+    }
+
+    #[inline]
+    fn woe(&self) -> f64 {
+        self.woe
+    }
+
+    #[inline]
+    fn iv(&self) -> f64 {
+        self.iv
+    }
+
+    #[inline]
+    fn counts(&self) -> (i32, i32) {
+        (self.pos, self.neg)
+    }
+
+    fn recount(&mut self, pos: i32, neg: i32, woe: f64, iv: f64) {
+        (self.pos, self.neg) = (pos, neg);
+        (self.woe, self.iv) = (woe, iv);
+        self.weights = None;
+    }
+
+    fn set_pvalue(&mut self, pvalue: f64) {
+        self.pvalue = Some(pvalue);
+    }
+}
+
+/// Objective of the optimized bins; missing and special bins are excluded.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FitScore {
+    /// IV, or the configured `Divergence` when it is not IV.
+    pub iv: f64,
+    /// `iv` minus the regularization penalty, the quantity the DP maximizes.
+    pub penalized: f64,
+}
+
+/// The exact optimum next to the fast solution, both under the fitted trend.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OptimalityGap {
+    pub exact: FitScore,
+    /// `None` when the fast DP found no bins within the size constraints.
+    pub fast: Option<FitScore>,
+}
+
+impl OptimalityGap {
+    /// Penalized score the fast solution leaves on the table; infinite when
+    /// it found no solution.
+    pub fn absolute(&self) -> f64 {
+        self.fast
+            .map_or(f64::INFINITY, |fast| self.exact.penalized - fast.penalized)
+    }
+
+    /// `absolute` relative to the exact penalized score.
+    pub fn relative(&self) -> f64 {
+        self.absolute() / self.exact.penalized.abs().max(f64::EPSILON)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NumericalModel {
+    pub config: NumericalConfig,
+    pub bins: Vec<NumBin>,
+    /// Trend the bins were fitted under.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trend: Option<Trend>,
+    /// `None` when no trend admits bins within the size constraints and the
+    /// solver fell back to a single bin.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<FitScore>,
+    /// Set when fitted with `Solver::Exact`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gap: Option<OptimalityGap>,
+    /// Prebin statistics kept for editing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prebins: Option<PreNumBinStats>,
+}
+
+impl NumericalModel {
+    pub fn transform(&self, x: ArrayView1<f64>) -> Vec<f64> {
+        NumericalBinning::execute_transform(x, &self.bins)
+    }
+
+    pub fn total_iv(&self) -> f64 {
+        self.bins.iter().map(|b| b.iv).sum()
+    }
+
+    /// Stability of `x` against the fitted bins, with the outcome drift per
+    /// bin when `y` is given.
+    pub fn stability(
+        &self,
+        x: ArrayView1<f64>,
+        y: Option<ArrayView1<i32>>,
+    ) -> Result<Stability, BinningError> {
+        validate::check_new_sample(x.iter().map(|v| v.is_infinite()))?;
+        if let Some(y) = y {
+            validate::check_new_target(x.len(), y.iter())?;
+        }
+        let fitted: Vec<Fitted> = self
+            .bins
+            .iter()
+            .map(|b| Fitted {
+                bin_id: b.bin_id,
+                woe: b.woe,
+                iv: b.iv,
+                weight: b.total_weight(),
+            })
+            .collect();
+        let positions = NumericalBinning::bin_positions(x, &self.bins);
+        let targets = (0..x.len()).map(|i| y.map(|y| y[i]));
+        Ok(stability::compare(
+            &fitted,
+            positions.into_iter().zip(targets),
+            y.is_some(),
+        ))
+    }
+
+    /// Recomputes the bin table on `x` and `y` without refitting, flagging
+    /// the bins whose WoE changed sign or order.
+    pub fn evaluate(
+        &self,
+        x: ArrayView1<f64>,
+        y: ArrayView1<i32>,
+    ) -> Result<Evaluation<NumBin>, BinningError> {
+        validate::check_binary(x.iter().map(|v| (v.is_nan(), false)), y.iter())?;
+        let positions = NumericalBinning::bin_positions(x, &self.bins);
+        Ok(evaluate::evaluate(
+            &self.bins,
+            positions.into_iter().zip(y.iter().copied()),
+            self.config.significance,
+        ))
+    }
+
+    /// Merges regular bin `bin_id` with the one after it and returns the
+    /// constraints the edited bins break.
+    pub fn merge_bins(&mut self, bin_id: usize) -> Result<Vec<Violation>, BinningError> {
+        let mut splits = self.split_indices()?;
+        edit::check_mergeable(bin_id, splits.len() + 1)?;
+        splits.remove(bin_id);
+        self.rebuild(splits)
+    }
+
+    /// Splits regular bin `bin_id` at `value`, rounded down to the nearest
+    /// prebin boundary, and returns the constraints the edited bins break.
+    pub fn split_bin(&mut self, bin_id: usize, value: f64) -> Result<Vec<Violation>, BinningError> {
+        let mut splits = self.split_indices()?;
+        edit::check_regular(bin_id, splits.len() + 1)?;
+        let stats = self.prebins.as_ref().ok_or_else(edit::no_prebins)?;
+        let start = if bin_id == 0 {
+            0
+        } else {
+            splits[bin_id - 1] + 1
+        };
+        let end = splits.get(bin_id).copied().unwrap_or(stats.edges.len() - 1);
+        let at = stats.edges.partition_point(|&e| e <= value);
+        if !value.is_finite() || at <= start || at > end {
+            return Err(BinningError::InvalidInput(format!(
+                "No prebin boundary at or below {} falls inside bin {}",
+                value, bin_id
+            )));
+        }
+        splits.insert(bin_id, at - 1);
+        self.rebuild(splits)
+    }
+
+    /// Constraints the current regular bins break.
+    pub fn violations(&self) -> Vec<Violation> {
+        let regular: Vec<Checked> = self
+            .bins
+            .iter()
+            .filter(|b| !b.is_missing && !b.is_special())
+            .map(|b| Checked {
+                bin_id: b.bin_id,
+                woe: b.woe,
+                event_rate: b.event_rate(),
+                weight: b.total_weight(),
+                pos: b.pos,
+                neg: b.neg,
+            })
+            .collect();
+        Constraints {
+            max_bins: self.config.max_bins,
+            min_bin_pct: self.config.min_bin_pct,
+            max_bin_pct: self.config.max_bin_pct,
+            event_limits: self.config.event_limits,
+            significance: self.config.significance,
+            separation: self.config.separation,
+            trend: self.trend,
+        }
+        .check(&regular)
+    }
+
+    /// Prebin index each regular bin but the last ends at.
+    fn split_indices(&self) -> Result<Vec<usize>, BinningError> {
+        let stats = self.prebins.as_ref().ok_or_else(edit::no_prebins)?;
+        let regular: Vec<&NumBin> = self
+            .bins
+            .iter()
+            .filter(|b| !b.is_missing && !b.is_special())
+            .collect();
+        regular[..regular.len().saturating_sub(1)]
+            .iter()
+            .map(|b| {
+                stats
+                    .edges
+                    .binary_search_by(|e| e.total_cmp(&b.range.1))
+                    .map_err(|_| edit::no_prebins())
+            })
+            .collect()
+    }
+
+    fn rebuild(&mut self, splits: Vec<usize>) -> Result<Vec<Violation>, BinningError> {
+        let stats = self.prebins.as_ref().ok_or_else(edit::no_prebins)?;
+        let binning = NumericalBinning::new(self.config.clone());
+        self.score = Some(binning.score_splits(stats, &splits));
+        self.bins = binning.reconstruct_bins(stats, splits);
+        self.gap = None;
+        Ok(self.violations())
+    }
+
+    pub fn to_json(&self) -> Result<String, BinningError> {
+        json::to_json("numerical", self)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, BinningError> {
+        let model: Self = json::from_json("numerical", json)?;
+        model.config.validate()?;
+        Ok(model)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NumericalBinning {
+    pub config: NumericalConfig,
+}
+
+/// Last prebin a bin starting at each prebin may reach without crossing a
+/// locked cut point.
+fn bin_limits(locked: &[usize], n: usize) -> Vec<usize> {
+    (0..n)
+        .map(|start| {
+            let next = locked.partition_point(|&l| l < start);
+            locked.get(next).copied().unwrap_or(n - 1)
+        })
+        .collect()
+}
+
+impl NumericalBinning {
+    pub fn new(config: NumericalConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn fit(
+        &self,
+        x: ArrayView1<f64>,
+        y: ArrayView1<i32>,
+    ) -> Result<NumericalModel, BinningError> {
+        self.execute_fit(x, y, None)
+    }
+
+    pub fn fit_weighted(
+        &self,
+        x: ArrayView1<f64>,
+        y: ArrayView1<i32>,
+        sample_weight: ArrayView1<f64>,
+    ) -> Result<NumericalModel, BinningError> {
+        self.execute_fit(x, y, Some(sample_weight))
+    }
+
+    pub fn execute_fit(
+        &self,
+        x: ArrayView1<f64>,
+        y: ArrayView1<i32>,
+        sample_weight: Option<ArrayView1<f64>>,
+    ) -> Result<NumericalModel, BinningError> {
+        let special = SpecialLookup::new(&self.config.special_codes);
+        validate::check_binary(
+            x.iter()
+                .map(|&v| (v.is_nan() || special.find(v).is_some(), v.is_infinite())),
+            y.iter(),
+        )?;
+        if let Some(w) = sample_weight {
+            validate::check_weights(
+                w.iter(),
+                x.iter()
+                    .zip(y.iter())
+                    .map(|(&v, &t)| (v.is_nan() || special.find(v).is_some(), t)),
+            )?;
+        }
+
+        let stats = match sample_weight {
+            Some(w) => self.prebinning_weighted(x, y, w, &special),
+            None => self.prebinning(x, y, &special),
+        };
+        let cuts = self.cut_indices(&stats)?;
+        if let UserSplits::Fixed(_) = self.config.user_splits {
+            return Ok(NumericalModel {
+                config: self.config.clone(),
+                score: Some(self.score_splits(&stats, &cuts)),
+                bins: self.reconstruct_bins(&stats, cuts),
+                trend: None,
+                gap: None,
+                prebins: Some(stats),
+            });
+        }
+        // Any cut points left are locked.
+        self.check_locked(&stats, &cuts)?;
+        let limits = bin_limits(&cuts, stats.edges.len());
+
+        let (mut best_trend, mut best_score, mut best_indices) = self.solve(&stats, &limits);
+        let gap = match (self.config.solver, best_score) {
+            (Solver::Exact, Some(exact)) => Some(OptimalityGap {
+                exact,
+                fast: self
+                    .split(&stats, best_trend, &limits)
+                    .map(|(score, _)| score),
+            }),
+            _ => None,
+        };
+        if best_score.is_none()
+            && let Some(significance) = self.config.significance
+        {
+            // No binning separates every adjacent pair, so solve without the
+            // limit and merge the least significant pairs afterwards.
+            let relaxed = NumericalBinning::new(NumericalConfig {
+                significance: None,
+                ..self.config.clone()
+            });
+            let (trend, score, split_indices) = relaxed.solve(&stats, &limits);
+            if score.is_some() {
+                let max_samples =
+                    ((stats.total_wpos + stats.total_wneg) * self.config.max_bin_pct).floor();
+                best_indices = significance::merge_insignificant(
+                    &significance,
+                    split_indices,
+                    stats.edges.len(),
+                    &cuts,
+                    |start, end| stats.get_counts(start, end),
+                    |start, end| {
+                        let (wpos, wneg) = stats.get_weights(start, end);
+                        let (pos, neg) = stats.get_counts(start, end);
+                        wpos + wneg <= max_samples && self.config.event_limits.allows(pos, neg)
+                    },
+                );
+                best_trend = trend;
+                best_score = Some(self.score_splits(&stats, &best_indices));
+            }
+        }
+        if best_score.is_none() && !self.config.event_limits.is_none() {
+            return Err(self
+                .config
+                .event_limits
+                .infeasible(stats.total_pos, stats.total_neg));
+        }
+        if best_score.is_none() && !cuts.is_empty() {
+            return Err(BinningError::InvalidInput(
+                "The locked splits violate the monotonic trend or the bin size constraints"
+                    .to_string(),
+            ));
+        }
+        Ok(NumericalModel {
+            config: self.config.clone(),
+            bins: self.reconstruct_bins(&stats, best_indices),
+            trend: Some(best_trend),
+            score: best_score,
+            gap,
+            prebins: Some(stats),
+        })
+    }
+
+    /// Best solution over the candidate trends; the score is `None` when no
+    /// trend is feasible.
+    fn solve(
+        &self,
+        stats: &PreNumBinStats,
+        limits: &[usize],
+    ) -> (Trend, Option<FitScore>, Vec<usize>) {
+        let candidates = self.candidate_trends(stats);
+        let mut best_trend = candidates[0];
+        let mut best_score: Option<FitScore> = None;
+        let mut best_indices = Vec::new();
+        for trend in candidates {
+            let solution = match self.config.solver {
+                Solver::Fast => self.split(stats, trend, limits),
+                Solver::Exact => self.split_exact(stats, trend, limits),
+            };
+            if let Some((score, split_indices)) = solution
+                && best_score.is_none_or(|best| score.iv > best.iv)
+            {
+                best_trend = trend;
+                best_score = Some(score);
+                best_indices = split_indices;
+            }
+        }
+        (best_trend, best_score, best_indices)
+    }
+
+    /// Prebin index each user cut point closes, as produced by the prebinning.
+    fn cut_indices(&self, stats: &PreNumBinStats) -> Result<Vec<usize>, BinningError> {
+        let n = stats.edges.len();
+        self.config
+            .user_splits
+            .splits()
+            .iter()
+            .map(
+                |&cut| match stats.edges.binary_search_by(|e| e.total_cmp(&cut)) {
+                    Ok(i) if i < n - 1 => Ok(i),
+                    _ => Err(BinningError::InvalidInput(format!(
+                        "User split {} leaves an empty bin",
+                        cut
+                    ))),
+                },
+            )
+            .collect()
+    }
+
+    /// Locked cut points can only be refined, so every segment between them
+    /// must already meet `min_bin_pct`.
+    fn check_locked(&self, stats: &PreNumBinStats, cuts: &[usize]) -> Result<(), BinningError> {
+        let total_samples = stats.total_wpos + stats.total_wneg;
+        let min_samples = (total_samples * self.config.min_bin_pct).floor();
+        let mut start = 0;
+        for end in cuts.iter().copied().chain([stats.edges.len() - 1]) {
+            let (p, n_c) = stats.get_weights(start, end);
+            if p + n_c < min_samples {
+                return Err(BinningError::InvalidInput(format!(
+                    "The locked splits leave a bin with {:.4} of the rows, below min_bin_pct ({})",
+                    (p + n_c) / total_samples,
+                    self.config.min_bin_pct
+                )));
+            }
+            start = end + 1;
+        }
+        Ok(())
+    }
+
+    /// Score of the bins closed at `splits`, penalized as the DP would.
+    fn score_splits(&self, stats: &PreNumBinStats, splits: &[usize]) -> FitScore {
+        let n = stats.edges.len();
+        let k_max = self.config.max_bins.min(n).max(splits.len() + 1);
+        let total_samples = stats.total_wpos + stats.total_wneg;
+        let regularization = &self.config.regularization;
+        let target_pct = self.config.target_bin_pct();
+        let range_width = (self.config.max_bin_pct - self.config.min_bin_pct) / 2.0;
+
+        let (mut iv, mut penalized) = (0.0, 0.0);
+        let mut start = 0;
+        for (k, end) in (1..).zip(splits.iter().copied().chain([n - 1])) {
+            let (p, n_c) = stats.get_weights(start, end);
+            let adaptive_lambda = regularization.strength * ((k_max - k + 1) as f64 / k_max as f64);
+            let penalty = adaptive_lambda
+                * regularization.penalty((p + n_c) / total_samples, target_pct, range_width);
+            let bin_iv = stats.calc_iv_range(start, end);
+            iv += bin_iv;
+            penalized += bin_iv - penalty;
+            start = end + 1;
+        }
+        FitScore { iv, penalized }
+    }
+
+    /// Trends tried by the solver, in tie-breaking order.
+    fn candidate_trends(&self, stats: &PreNumBinStats) -> Vec<Trend> {
+        match self.config.monotonic_trend {
+            MonotonicTrend::Ascending => vec![Trend::Increasing],
+            MonotonicTrend::Descending => vec![Trend::Decreasing],
+            MonotonicTrend::Peak => vec![Trend::Peak],
+            MonotonicTrend::Valley => vec![Trend::Valley],
+            MonotonicTrend::Auto => vec![
+                Trend::Increasing,
+                Trend::Decreasing,
+                Trend::Peak,
+                Trend::Valley,
+            ],
+            MonotonicTrend::AutoAscDesc => vec![Trend::Increasing, Trend::Decreasing],
+            MonotonicTrend::AutoHeuristic => vec![Self::heuristic_trend(stats)],
+            MonotonicTrend::None => vec![Trend::Unconstrained],
+        }
+    }
+
+    /// Guesses the trend from the WoE of ten equal-weight groups of prebins.
+    /// An interior extreme that clears both ends by a tenth of the WoE range
+    /// makes it a peak or valley; otherwise the ends decide the direction.
+    fn heuristic_trend(stats: &PreNumBinStats) -> Trend {
+        let n = stats.edges.len();
+        let total = stats.total_wpos + stats.total_wneg;
+        let n_groups = n.min(10);
+
+        let mut woes = Vec::with_capacity(n_groups);
+        let mut start = 0;
+        for g in 1..=n_groups {
+            if start >= n {
+                break;
+            }
+            let target = total * g as f64 / n_groups as f64;
+            let mut end = start;
+            while end < n - 1 {
+                let (p, n_c) = stats.get_weights(0, end);
+                if p + n_c >= target {
+                    break;
+                }
+                end += 1;
+            }
+            let (p, n_c) = stats.get_weights(start, end);
+            woes.push(stats.calc_woe_single(p, n_c));
+            start = end + 1;
+        }
+
+        let len = woes.len();
+        let (first, last) = (woes[0], woes[len - 1]);
+        let (i_max, max) =
+            woes.iter()
+                .copied()
+                .enumerate()
+                .fold(
+                    (0, f64::NEG_INFINITY),
+                    |acc, (i, w)| if w > acc.1 { (i, w) } else { acc },
+                );
+        let (i_min, min) =
+            woes.iter()
+                .copied()
+                .enumerate()
+                .fold(
+                    (0, f64::INFINITY),
+                    |acc, (i, w)| if w < acc.1 { (i, w) } else { acc },
+                );
+        let tol = 0.1 * (max - min);
+        let is_interior = |i: usize| i > 0 && i + 1 < len;
+
+        let peak = if is_interior(i_max) {
+            max - first.max(last)
+        } else {
+            0.0
+        };
+        let valley = if is_interior(i_min) {
+            first.min(last) - min
+        } else {
+            0.0
+        };
+        if peak > tol || valley > tol {
+            if peak >= valley {
+                Trend::Peak
+            } else {
+                Trend::Valley
+            }
+        } else if last >= first {
+            Trend::Increasing
+        } else {
+            Trend::Decreasing
+        }
+    }
+
+    fn prebinning(
+        &self,
+        x: ArrayView1<f64>,
+        y: ArrayView1<i32>,
+        special: &SpecialLookup,
+    ) -> PreNumBinStats {
+        let (mut missing_pos, mut missing_neg) = (0, 0);
+        let mut special_tally = vec![SpecialTally::default(); self.config.special_codes.len()];
+        let mut data: Vec<(f64, i32)> = Vec::with_capacity(x.len());
+        for (&v, &t) in x.iter().zip(y.iter()) {
+            if v.is_nan() {
+                if t == 1 {
+                    missing_pos += 1;
+                } else {
+                    missing_neg += 1;
+                }
+                continue;
+            }
+            if let Some(g) = special.find(v) {
+                special_tally[g].push(t, 1.0);
+                continue;
+            }
+            data.push((v, t));
+        }
+
+        data.par_sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut pos_counts: Vec<i32> = Vec::new();
+        let mut neg_counts: Vec<i32> = Vec::new();
+        let mut edges: Vec<f64> = Vec::new();
+
+        let mut start = 0;
+        let cuts = self.config.user_splits.splits();
+        let ends = prebinning::with_cuts(prebin_ends(&self.config.prebinning, &data), &data, cuts);
+        for end in ends {
+            let chunk = &data[start..=end];
+            let curr_p = chunk.iter().filter(|&&(_, t)| t == 1).count() as i32;
+            pos_counts.push(curr_p);
+            neg_counts.push(chunk.len() as i32 - curr_p);
+            edges.push(prebinning::edge(&data, end, cuts));
+            start = end + 1;
+        }
+        PreNumBinStats::new(&pos_counts, &neg_counts, edges, missing_pos, missing_neg)
+            .with_special(special_tally)
+            .with_divergence(self.config.divergence)
+    }
+
+    fn prebinning_weighted(
+        &self,
+        x: ArrayView1<f64>,
+        y: ArrayView1<i32>,
+        w: ArrayView1<f64>,
+        special: &SpecialLookup,
+    ) -> PreNumBinStats {
+        let (mut missing_pos, mut missing_neg) = (0, 0);
+        let mut missing_weights = BinWeights { pos: 0.0, neg: 0.0 };
+        let mut special_tally = vec![SpecialTally::default(); self.config.special_codes.len()];
+        let mut data: Vec<(f64, i32, f64)> = Vec::with_capacity(x.len());
+        for ((&v, &t), &wt) in x.iter().zip(y.iter()).zip(w.iter()) {
+            if v.is_nan() {
+                if t == 1 {
+                    missing_pos += 1;
+                    missing_weights.pos += wt;
+                } else {
+                    missing_neg += 1;
+                    missing_weights.neg += wt;
+                }
+                continue;
+            }
+            if let Some(g) = special.find(v) {
+                special_tally[g].push(t, wt);
+                continue;
+            }
+            data.push((v, t, wt));
+        }
+
+        data.par_sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut pos_counts: Vec<i32> = Vec::new();
+        let mut neg_counts: Vec<i32> = Vec::new();
+        let mut pos_weights: Vec<f64> = Vec::new();
+        let mut neg_weights: Vec<f64> = Vec::new();
+        let mut edges: Vec<f64> = Vec::new();
+
+        let mut start = 0;
+        let cuts = self.config.user_splits.splits();
+        let ends = prebinning::with_cuts(prebin_ends(&self.config.prebinning, &data), &data, cuts);
+        for end in ends {
+            let (mut curr_p, mut curr_n): (i32, i32) = (0, 0);
+            let (mut curr_wp, mut curr_wn): (f64, f64) = (0.0, 0.0);
+            for &(_, target, weight) in &data[start..=end] {
+                if target == 1 {
+                    curr_p += 1;
+                    curr_wp += weight;
+                } else {
+                    curr_n += 1;
+                    curr_wn += weight;
+                }
+            }
+            pos_counts.push(curr_p);
+            neg_counts.push(curr_n);
+            pos_weights.push(curr_wp);
+            neg_weights.push(curr_wn);
+            edges.push(prebinning::edge(&data, end, cuts));
+            start = end + 1;
+        }
+        PreNumBinStats::new(&pos_counts, &neg_counts, edges, missing_pos, missing_neg)
+            .with_weights(&pos_weights, &neg_weights, missing_weights)
+            .with_special(special_tally)
+            .with_divergence(self.config.divergence)
+    }
+
+    fn split(
+        &self,
+        stats: &PreNumBinStats,
+        trend: Trend,
+        limits: &[usize],
+    ) -> Option<(FitScore, Vec<usize>)> {
+        let n = stats.edges.len();
+        let k_max = self.config.max_bins.min(n);
+        let n_phases = trend.n_phases();
+        let total_samples = stats.total_wpos + stats.total_wneg;
+        let min_samples = (total_samples * self.config.min_bin_pct).floor();
+        let max_samples = (total_samples * self.config.max_bin_pct).floor();
+        let event_limits = &self.config.event_limits;
+        let significance = self.config.significance;
+        let separation = self.config.separation;
+        let regularization = &self.config.regularization;
+        let target_pct = self.config.target_bin_pct();
+        let range_width = (self.config.max_bin_pct - self.config.min_bin_pct) / 2.0;
+
+        let shape = (k_max + 1, n, n_phases);
+        let mut dp_score = Array3::<f64>::from_elem(shape, f64::NEG_INFINITY);
+        let mut dp_iv = Array3::<f64>::from_elem(shape, 0.0);
+        let mut last_woe = Array3::<f64>::from_elem(shape, 0.0);
+        let mut best_split = Array3::<usize>::from_elem(shape, 0);
+        let mut best_phase = Array3::<usize>::from_elem(shape, 0);
+
+        let lambda = regularization.strength;
+        for i in 0..n {
+            let (p, n_c) = stats.get_weights(0, i);
+            let current_count = p + n_c;
+            let (pos, neg) = stats.get_counts(0, i);
+
+            if i <= limits[0]
+                && current_count >= min_samples
+                && current_count <= max_samples
+                && event_limits.allows(pos, neg)
+            {
+                let current_pct = current_count / total_samples;
+                let penalty = lambda * regularization.penalty(current_pct, target_pct, range_width);
+                let iv = stats.calc_iv_range(0, i);
+                dp_score[[1, i, 0]] = iv - penalty;
+                dp_iv[[1, i, 0]] = iv;
+                last_woe[[1, i, 0]] = stats.calc_woe_single(p, n_c);
+            }
+        }
+
+        for k in 2..=k_max {
+            let adaptive_lambda = lambda * ((k_max - k + 1) as f64 / k_max as f64);
+
+            for i in (k - 1)..n {
+                for j in (k - 2)..i {
+                    if i > limits[j + 1] {
+                        continue;
+                    }
+                    let (cur_p, cur_n) = stats.get_weights(j + 1, i);
+                    let cur_count = cur_p + cur_n;
+                    if cur_count < min_samples || cur_count > max_samples {
+                        continue;
+                    }
+                    let (pos, neg) = stats.get_counts(j + 1, i);
+                    if !event_limits.allows(pos, neg) {
+                        continue;
+                    }
+
+                    let cur_woe = stats.calc_woe_single(cur_p, cur_n);
+                    let cur_pct = cur_count / total_samples;
+                    let penalty =
+                        adaptive_lambda * regularization.penalty(cur_pct, target_pct, range_width);
+                    let iv = stats.calc_iv_range(j + 1, i);
+                    let cur_score = iv - penalty;
+
+                    for from in 0..n_phases {
+                        if dp_score[[k - 1, j, from]] == f64::NEG_INFINITY {
+                            continue;
+                        }
+                        let prev_woe = last_woe[[k - 1, j, from]];
+                        if significance.is_some() || !separation.is_none() {
+                            let prev_start = if k == 2 {
+                                0
+                            } else {
+                                best_split[[k - 1, j, from]] + 1
+                            };
+                            if let Some(significance) = significance
+                                && !significance
+                                    .separates(stats.get_counts(prev_start, j), (pos, neg))
+                            {
+                                continue;
+                            }
+                            let (prev_p, prev_n) = stats.get_weights(prev_start, j);
+                            if !separation.separates(
+                                (prev_woe, calc_event_rate(prev_p, prev_n)),
+                                (cur_woe, calc_event_rate(cur_p, cur_n)),
+                            ) {
+                                continue;
+                            }
+                        }
+
+                        for to in from..n_phases {
+                            if !trend.allows(from, to, prev_woe, cur_woe) {
+                                continue;
+                            }
+                            let total_score = dp_score[[k - 1, j, from]] + cur_score;
+                            if total_score > dp_score[[k, i, to]] {
+                                dp_score[[k, i, to]] = total_score;
+                                dp_iv[[k, i, to]] = dp_iv[[k - 1, j, from]] + iv;
+                                best_split[[k, i, to]] = j;
+                                best_phase[[k, i, to]] = from;
+                                last_woe[[k, i, to]] = cur_woe;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Peak and valley must have turned by the last bin.
+        let final_phase = n_phases - 1;
+        let mut final_k = 1;
+        let mut max_score = f64::NEG_INFINITY;
+
+        for k in 1..=k_max {
+            if dp_score[[k, n - 1, final_phase]] > max_score {
+                max_score = dp_score[[k, n - 1, final_phase]];
+                final_k = k;
+            }
+        }
+        if max_score == f64::NEG_INFINITY {
+            return None;
+        }
+
+        let mut splits = Vec::new();
+        let mut curr_i = n - 1;
+        let mut phase = final_phase;
+        let mut k_ptr = final_k;
+        while k_ptr > 1 {
+            let split_pt = best_split[[k_ptr, curr_i, phase]];
+            phase = best_phase[[k_ptr, curr_i, phase]];
+            splits.push(split_pt);
+            curr_i = split_pt;
+            k_ptr -= 1;
+        }
+        splits.sort();
+
+        let score = FitScore {
+            iv: dp_iv[[final_k, n - 1, final_phase]],
+            penalized: max_score,
+        };
+        Some((score, splits))
+    }
+
+    /// Solves the same problem as `split` to optimality. The state is the last
+    /// bin itself rather than its end point, so no feasible path is dropped in
+    /// favour of a better-scoring one with a more restrictive last WoE. For each
+    /// end point the predecessors are sorted by WoE, so the best compatible one
+    /// is a binary search away: O(k_max * n^2 * log n) time.
+    fn split_exact(
+        &self,
+        stats: &PreNumBinStats,
+        trend: Trend,
+        limits: &[usize],
+    ) -> Option<(FitScore, Vec<usize>)> {
+        let n = stats.edges.len();
+        let k_max = self.config.max_bins.min(n);
+        let n_phases = trend.n_phases();
+        let total_samples = stats.total_wpos + stats.total_wneg;
+        let min_samples = (total_samples * self.config.min_bin_pct).floor();
+        let max_samples = (total_samples * self.config.max_bin_pct).floor();
+        let event_limits = &self.config.event_limits;
+        let significance = self.config.significance;
+        let separation = self.config.separation;
+        let regularization = &self.config.regularization;
+        let target_pct = self.config.target_bin_pct();
+        let range_width = (self.config.max_bin_pct - self.config.min_bin_pct) / 2.0;
+        let lambda = regularization.strength;
+
+        // (woe, iv - penalty, iv) of prebins `start..=end` as the k-th bin.
+        let bin = |k: usize, start: usize, end: usize| -> Option<(f64, f64, f64)> {
+            if end > limits[start] {
+                return None;
+            }
+            let (p, n_c) = stats.get_weights(start, end);
+            let count = p + n_c;
+            if count < min_samples || count > max_samples {
+                return None;
+            }
+            let (pos, neg) = stats.get_counts(start, end);
+            if !event_limits.allows(pos, neg) {
+                return None;
+            }
+            let adaptive_lambda = lambda * ((k_max - k + 1) as f64 / k_max as f64);
+            let penalty = adaptive_lambda
+                * regularization.penalty(count / total_samples, target_pct, range_width);
+            let iv = stats.calc_iv_range(start, end);
+            Some((stats.calc_woe_single(p, n_c), iv - penalty, iv))
+        };
+
+        // score[[start, end, phase]] of the best path whose last bin is `start..=end`.
+        let mut score = Array3::<f64>::from_elem((n, n, n_phases), f64::NEG_INFINITY);
+        let mut back_start = Array4::<u32>::zeros((k_max + 1, n, n, n_phases));
+        let mut back_phase = Array4::<u8>::zeros((k_max + 1, n, n, n_phases));
+
+        let mut best: Option<(f64, usize, usize, usize)> = None;
+        let mut record_final = |k: usize, score: &Array3<f64>| {
+            for start in 0..n {
+                let s = score[[start, n - 1, n_phases - 1]];
+                if s > best.map_or(f64::NEG_INFINITY, |b| b.0) {
+                    best = Some((s, k, start, n_phases - 1));
+                }
+            }
+        };
+
+        for end in 0..n {
+            if let Some((_, s, _)) = bin(1, 0, end) {
+                score[[0, end, 0]] = s;
+            }
+        }
+        record_final(1, &score);
+
+        for k in 2..=k_max {
+            let mut next = Array3::<f64>::from_elem((n, n, n_phases), f64::NEG_INFINITY);
+            for end in (k - 2)..n.saturating_sub(1) {
+                for from in 0..n_phases {
+                    let mut preds: Vec<(f64, f64, usize)> = (0..=end)
+                        .filter(|&start| score[[start, end, from]] > f64::NEG_INFINITY)
+                        .map(|start| {
+                            let (p, n_c) = stats.get_weights(start, end);
+                            (
+                                stats.calc_woe_single(p, n_c),
+                                score[[start, end, from]],
+                                start,
+                            )
+                        })
+                        .collect();
+                    if preds.is_empty() {
+                        continue;
+                    }
+                    preds.sort_by(|a, b| a.0.total_cmp(&b.0));
+                    // With a significance or separation limit the best compatible
+                    // predecessor also depends on its counts, so it is found by
+                    // scanning them from the highest score down.
+                    let scan = significance.is_some() || !separation.is_none();
+                    let mut by_score = Vec::new();
+                    if scan {
+                        by_score = preds.clone();
+                        by_score.sort_by(|a, b| b.1.total_cmp(&a.1));
+                    }
+
+                    // Best predecessor among the lowest `i + 1` and the highest
+                    // `len - i` WoEs.
+                    let mut prefix = Vec::with_capacity(preds.len());
+                    let mut acc = (f64::NEG_INFINITY, 0);
+                    for &(_, s, start) in &preds {
+                        if s > acc.0 {
+                            acc = (s, start);
+                        }
+                        prefix.push(acc);
+                    }
+                    let mut suffix = vec![(f64::NEG_INFINITY, 0); preds.len()];
+                    let mut acc = (f64::NEG_INFINITY, 0);
+                    for (i, &(_, s, start)) in preds.iter().enumerate().rev() {
+                        if s > acc.0 {
+                            acc = (s, start);
+                        }
+                        suffix[i] = acc;
+                    }
+
+                    for last in (end + 1)..n {
+                        let Some((cur_woe, cur_score, _)) = bin(k, end + 1, last) else {
+                            continue;
+                        };
+                        let counts = stats.get_counts(end + 1, last);
+                        let (cur_p, cur_n) = stats.get_weights(end + 1, last);
+                        let cur_rate = calc_event_rate(cur_p, cur_n);
+                        for to in from..n_phases {
+                            let pred = if scan {
+                                by_score
+                                    .iter()
+                                    .find(|&&(woe, _, start)| {
+                                        let (p, n_c) = stats.get_weights(start, end);
+                                        trend.allows(from, to, woe, cur_woe)
+                                            && significance.is_none_or(|s| {
+                                                s.separates(stats.get_counts(start, end), counts)
+                                            })
+                                            && separation.separates(
+                                                (woe, calc_event_rate(p, n_c)),
+                                                (cur_woe, cur_rate),
+                                            )
+                                    })
+                                    .map(|&(_, s, start)| (s, start))
+                            } else {
+                                match trend.step(from, to) {
+                                    Some(Step::Up) => {
+                                        let i = preds
+                                            .partition_point(|p| p.0 <= cur_woe + f64::EPSILON);
+                                        if i == 0 { None } else { Some(prefix[i - 1]) }
+                                    }
+                                    Some(Step::Down) => {
+                                        let i =
+                                            preds.partition_point(|p| p.0 < cur_woe - f64::EPSILON);
+                                        suffix.get(i).copied()
+                                    }
+                                    Some(Step::Any) => Some(prefix[preds.len() - 1]),
+                                    None => None,
+                                }
+                            };
+                            let Some((pred_score, pred_start)) = pred else {
+                                continue;
+                            };
+                            let total = pred_score + cur_score;
+                            if total > next[[end + 1, last, to]] {
+                                next[[end + 1, last, to]] = total;
+                                back_start[[k, end + 1, last, to]] = pred_start as u32;
+                                back_phase[[k, end + 1, last, to]] = from as u8;
+                            }
+                        }
+                    }
+                }
+            }
+            score = next;
+            record_final(k, &score);
+        }
+
+        let (max_score, final_k, mut start, mut phase) = best?;
+        let mut splits = Vec::with_capacity(final_k - 1);
+        let mut end = n - 1;
+        let mut iv = 0.0;
+        for k in (1..=final_k).rev() {
+            iv += stats.calc_iv_range(start, end);
+            if k > 1 {
+                let prev_start = back_start[[k, start, end, phase]] as usize;
+                phase = back_phase[[k, start, end, phase]] as usize;
+                end = start - 1;
+                splits.push(end);
+                start = prev_start;
+            }
+        }
+        splits.sort();
+
+        let score = FitScore {
+            iv,
+            penalized: max_score,
+        };
+        Some((score, splits))
+    }
+
+    fn reconstruct_bins(&self, stats: &PreNumBinStats, splits: Vec<usize>) -> Vec<NumBin> {
+        let special_pos: f64 = stats.special.iter().map(|s| s.weights.pos).sum();
+        let special_neg: f64 = stats.special.iter().map(|s| s.weights.neg).sum();
+        let grand_total_pos = stats.total_wpos + stats.missing_weights.pos + special_pos;
+        let grand_total_neg = stats.total_wneg + stats.missing_weights.neg + special_neg;
+
+        let mut bins: Vec<NumBin> = Vec::new();
+        let n = stats.edges.len();
+        let mut start_idx = 0;
+        let mut all_splits = splits.clone();
+        all_splits.push(n - 1);
+
+        for (bin_id, &end_idx) in all_splits.iter().enumerate() {
+            let (pos, neg) = stats.get_counts(start_idx, end_idx);
+            let left = if start_idx == 0 {
+                f64::NEG_INFINITY
+            } else {
+                stats.edges[start_idx - 1]
+            };
+            let right = if end_idx == n - 1 {
+                f64::INFINITY
+            } else {
+                stats.edges[end_idx]
+            };
+
+            let (wpos, wneg) = stats.get_weights(start_idx, end_idx);
+            let (woe, iv) = calc_woe_iv(wpos, wneg, grand_total_pos, grand_total_neg);
+            let pvalue = match (self.config.significance, bins.last()) {
+                (Some(significance), Some(prev)) => Some(significance::pvalue(
+                    significance.test,
+                    (prev.pos, prev.neg),
+                    (pos, neg),
+                )),
+                _ => None,
+            };
+            bins.push(NumBin {
+                bin_id,
+                range: (left, right),
+                pos,
+                neg,
+                woe,
+                iv,
+                is_missing: false,
+                special: None,
+                pvalue,
+                weights: stats.is_weighted.then_some(BinWeights {
+                    pos: wpos,
+                    neg: wneg,
+                }),
+            });
+            start_idx = end_idx + 1;
+        }
+
+        for (group, tally) in self.config.special_codes.iter().zip(stats.special.iter()) {
+            let (woe, iv) = if tally.pos + tally.neg > 0 {
+                calc_woe_iv(
+                    tally.weights.pos,
+                    tally.weights.neg,
+                    grand_total_pos,
+                    grand_total_neg,
+                )
+            } else {
+                (0.0, 0.0)
+            };
+            bins.push(NumBin {
+                bin_id: bins.len(),
+                range: (f64::NAN, f64::NAN),
+                pos: tally.pos,
+                neg: tally.neg,
+                woe,
+                iv,
+                is_missing: false,
+                special: Some(group.clone()),
+                pvalue: None,
+                weights: stats.is_weighted.then_some(tally.weights),
+            });
+        }
+
+        if stats.missing_pos + stats.missing_neg > 0 {
+            let (woe, iv) = calc_woe_iv(
+                stats.missing_weights.pos,
+                stats.missing_weights.neg,
+                grand_total_pos,
+                grand_total_neg,
+            );
+            bins.push(NumBin {
+                bin_id: bins.len(),
+                range: (f64::NAN, f64::NAN),
+                pos: stats.missing_pos,
+                neg: stats.missing_neg,
+                woe,
+                iv,
+                is_missing: true,
+                special: None,
+                pvalue: None,
+                weights: stats.is_weighted.then_some(stats.missing_weights),
+            });
+        }
+        bins
+    }
+
+    pub fn execute_transform(x: ArrayView1<f64>, bins: &[NumBin]) -> Vec<f64> {
+        Self::bin_positions(x, bins)
+            .into_iter()
+            .map(|b| b.map_or(0.0, |b| bins[b].woe))
+            .collect()
+    }
+
+    /// Position in `bins` of the bin each value falls in; `None` for missing
+    /// values when there is no missing bin.
+    pub(crate) fn bin_positions(x: ArrayView1<f64>, bins: &[NumBin]) -> Vec<Option<usize>> {
+        let missing = bins.iter().position(|b| b.is_missing);
+        let special_bins: Vec<usize> = (0..bins.len()).filter(|&b| bins[b].is_special()).collect();
+        let special = SpecialLookup::new(
+            special_bins
+                .iter()
+                .filter_map(|&b| bins[b].special.as_ref()),
+        );
+        let regular: Vec<usize> = (0..bins.len())
+            .filter(|&b| !bins[b].is_missing && !bins[b].is_special())
+            .collect();
+        let thresholds: Vec<f64> = regular.iter().map(|&b| bins[b].range.1).collect();
+
+        x.iter()
+            .map(|&val| {
+                if val.is_nan() {
+                    missing
+                } else if let Some(g) = special.find(val) {
+                    Some(special_bins[g])
+                } else {
+                    let idx = thresholds
+                        .binary_search_by(|probe| {
+                            if probe < &val {
+                                std::cmp::Ordering::Less
+                            } else {
+                                std::cmp::Ordering::Greater
+                            }
+                        })
+                        .unwrap_err();
+                    Some(regular[idx])
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{
+        EventLimits, PenaltyFamily, PrebinMethod, Prebinning, Regularization, Separation,
+        UserSplits,
+    };
+    use crate::testutil::{Lcg, scores};
+    use ndarray::Array1;
+
+    fn fit(config: NumericalConfig, x: &Array1<f64>, y: &Array1<i32>) -> NumericalModel {
+        NumericalBinning::new(config)
+            .fit(x.view(), y.view())
+            .unwrap()
+    }
+
+    fn assert_counts_match(model: &NumericalModel, x: &Array1<f64>, y: &Array1<i32>) {
+        let recount = model.evaluate(x.view(), y.view()).unwrap();
+        for (fitted, new) in model.bins.iter().zip(&recount.bins) {
+            assert_eq!((fitted.pos, fitted.neg), (new.pos, new.neg));
+        }
+    }
+
+    #[test]
+    fn split_bin_counts_match_recount() {
+        let (x, y) = scores(5000, 7);
+        let mut model = fit(NumericalConfig::new(6, 0.05, 0.3).unwrap(), &x, &y);
+        assert!(model.bins.len() > 2);
+        let (left, right) = model.bins[1].range;
+        // Between two prebin edges, so the split has to round down.
+        let value = (left + right) / 2.0 + 0.05;
+        model.split_bin(1, value).unwrap();
+        assert_counts_match(&model, &x, &y);
+        let split = model.bins[1].range.1;
+        assert!(split > left && split < value);
+    }
+
+    #[test]
+    fn exact_solver_is_never_worse_than_fast() {
+        let mut n_compared = 0;
+        for (seed, n_prebins, trend) in [
+            (1, 8, MonotonicTrend::AutoAscDesc),
+            (2, 12, MonotonicTrend::Peak),
+            (3, 16, MonotonicTrend::None),
+            (4, 20, MonotonicTrend::Valley),
+        ] {
+            let (x, y) = scores(3000, seed);
+            let config = NumericalConfig::new(6, 0.05, 0.4)
+                .unwrap()
+                .with_prebinning(Prebinning {
+                    method: PrebinMethod::Quantile,
+                    n_bins: Some(n_prebins),
+                })
+                .unwrap()
+                .with_monotonic_trend(trend)
+                .with_solver(Solver::Exact);
+            let gap = fit(config, &x, &y).gap.unwrap();
+            // The fast solver may find no bins where the exact one does.
+            if let Some(fast) = gap.fast {
+                assert!(gap.exact.penalized >= fast.penalized - 1e-9);
+                n_compared += 1;
+            }
+        }
+        assert!(n_compared > 0);
+    }
+
+    #[test]
+    fn json_round_trip_keeps_bins_and_transform() {
+        let (x, y) = scores(3000, 5);
+        let mut x = x.to_vec();
+        x[..30].fill(f64::NAN);
+        let x = Array1::from(x);
+        let model = fit(NumericalConfig::new(6, 0.05, 0.3).unwrap(), &x, &y);
+        let json = model.to_json().unwrap();
+        let loaded = NumericalModel::from_json(&json).unwrap();
+        assert_eq!(loaded.to_json().unwrap(), json);
+        assert!(
+            loaded
+                .bins
+                .iter()
+                .any(|b| b.is_missing && b.range.0.is_nan())
+        );
+        assert_eq!(model.transform(x.view()), loaded.transform(x.view()));
+    }
+
+    #[test]
+    fn zero_weight_events_are_rejected() {
+        let (x, y) = scores(1000, 3);
+        let w = y.mapv(|t| if t == 1 { 0.0 } else { 1.0 });
+        let binning = NumericalBinning::new(NumericalConfig::new(6, 0.05, 0.3).unwrap());
+        assert!(matches!(
+            binning.fit_weighted(x.view(), y.view(), w.view()),
+            Err(BinningError::InvalidWeight(_))
+        ));
+    }
+
+    #[test]
+    fn auto_trends_pick_the_best_candidate() {
+        // Event rate peaking at x = 50, so a peak beats either direction.
+        let mut rng = Lcg::new(9);
+        let x: Vec<f64> = (0..4000)
+            .map(|_| (rng.uniform() * 1000.0).floor() / 10.0)
+            .collect();
+        let y: Vec<i32> = x
+            .iter()
+            .map(|&v| (rng.uniform() < 0.05 + 0.25 * (-((v - 50.0) / 15.0).powi(2)).exp()) as i32)
+            .collect();
+        let peaked = (Array1::from(x), Array1::from(y));
+        let monotone = scores(4000, 3);
+
+        // The trend a fit settled on and its IV, or `None` when infeasible.
+        let fit_trend = |trend: MonotonicTrend, (x, y): &(Array1<f64>, Array1<i32>)| {
+            let config = NumericalConfig::new(6, 0.05, 0.5)
+                .unwrap()
+                .with_monotonic_trend(trend);
+            let model = fit(config, x, y);
+            model.score.map(|score| (model.trend.unwrap(), score.iv))
+        };
+        // Ties go to the earlier candidate, as in the solver.
+        let best = |candidates: &[Option<(Trend, f64)>]| {
+            candidates
+                .iter()
+                .flatten()
+                .copied()
+                .reduce(|a, b| if b.1 > a.1 { b } else { a })
+        };
+        for data in [&peaked, &monotone] {
+            let forced = [
+                MonotonicTrend::Ascending,
+                MonotonicTrend::Descending,
+                MonotonicTrend::Peak,
+                MonotonicTrend::Valley,
+            ]
+            .map(|t| fit_trend(t, data));
+            assert_eq!(fit_trend(MonotonicTrend::Auto, data), best(&forced));
+            assert_eq!(
+                fit_trend(MonotonicTrend::AutoAscDesc, data),
+                best(&forced[..2])
+            );
+        }
+        assert_eq!(
+            fit_trend(MonotonicTrend::Auto, &peaked).unwrap().0,
+            Trend::Peak
+        );
+        assert_eq!(
+            fit_trend(MonotonicTrend::AutoAscDesc, &monotone).unwrap().0,
+            Trend::Decreasing
+        );
+    }
+
+    #[test]
+    fn size_penalties_pull_bin_shares_to_the_target() {
+        let (x, y) = scores(4000, 5);
+        let fit_with = |family, strength| {
+            let config = NumericalConfig::new(8, 0.02, 0.5)
+                .unwrap()
+                .with_monotonic_trend(MonotonicTrend::None)
+                .with_regularization(Regularization {
+                    family,
+                    strength,
+                    target_bin_pct: Some(0.25),
+                })
+                .unwrap();
+            fit(config, &x, &y)
+        };
+        // Largest distance of a regular bin's share from the target.
+        let spread = |model: &NumericalModel| {
+            let total: i32 = model.bins.iter().map(|b| b.pos + b.neg).sum();
+            model
+                .bins
+                .iter()
+                .filter(|b| !b.is_missing && !b.is_special())
+                .map(|b| ((b.pos + b.neg) as f64 / total as f64 - 0.25).abs())
+                .fold(0.0, f64::max)
+        };
+
+        let free = fit_with(PenaltyFamily::None, 5.0);
+        let score = free.score.unwrap();
+        assert_eq!(score.penalized, score.iv);
+        for family in [PenaltyFamily::LogBarrier, PenaltyFamily::Quadratic] {
+            let weak = fit_with(family, 0.01);
+            let strong = fit_with(family, 50.0);
+            let score = strong.score.unwrap();
+            assert!(score.penalized < score.iv, "{:?}", family);
+            assert!(spread(&strong) < spread(&weak), "{:?}", family);
+            assert!(spread(&strong) < 0.05, "{:?}: {}", family, spread(&strong));
+        }
+        // p ln p is negative, so the entropy family rewards even bins.
+        let score = fit_with(PenaltyFamily::Entropy, 1.0).score.unwrap();
+        assert!(score.penalized > score.iv);
+    }
+
+    #[test]
+    fn fixed_splits_are_the_bins() {
+        let (x, y) = scores(3000, 3);
+        let config = NumericalConfig::new(6, 0.05, 0.5)
+            .unwrap()
+            .with_user_splits(UserSplits::Fixed(vec![60.05, 25.05]))
+            .unwrap();
+        let model = fit(config, &x, &y);
+        let ranges: Vec<(f64, f64)> = model.bins.iter().map(|b| b.range).collect();
+        assert_eq!(
+            ranges,
+            [
+                (f64::NEG_INFINITY, 25.05),
+                (25.05, 60.05),
+                (60.05, f64::INFINITY)
+            ]
+        );
+        assert_eq!(model.trend, None);
+        assert_counts_match(&model, &x, &y);
+    }
+
+    #[test]
+    fn locked_splits_are_kept_and_refined() {
+        let (x, y) = scores(3000, 3);
+        let config = NumericalConfig::new(6, 0.05, 0.5)
+            .unwrap()
+            .with_user_splits(UserSplits::Locked(vec![50.05]))
+            .unwrap();
+        let model = fit(config, &x, &y);
+        assert!(model.bins.len() > 2);
+        assert!(model.bins.iter().any(|b| b.range.1 == 50.05));
+        assert_counts_match(&model, &x, &y);
+    }
+
+    #[test]
+    fn unusable_user_splits_are_rejected() {
+        let (x, y) = scores(3000, 3);
+        let fit_with = |user_splits, trend| {
+            let config = NumericalConfig::new(6, 0.05, 0.5)
+                .unwrap()
+                .with_monotonic_trend(trend)
+                .with_user_splits(user_splits)
+                .unwrap();
+            NumericalBinning::new(config).fit(x.view(), y.view())
+        };
+        let message = |result: Result<NumericalModel, BinningError>| match result {
+            Err(BinningError::InvalidInput(msg)) => msg,
+            other => panic!("expected InvalidInput, got {:?}", other.map(|m| m.bins)),
+        };
+
+        let past_the_data = fit_with(UserSplits::Fixed(vec![150.0]), MonotonicTrend::None);
+        assert!(message(past_the_data).contains("empty bin"));
+        let tiny_segment = fit_with(UserSplits::Locked(vec![0.55]), MonotonicTrend::None);
+        assert!(message(tiny_segment).contains("min_bin_pct"));
+        // The event rate falls with x, so cuts between well-separated
+        // segments cannot give an ascending WoE.
+        let against_trend = fit_with(
+            UserSplits::Locked(vec![30.05, 70.05]),
+            MonotonicTrend::Ascending,
+        );
+        assert!(message(against_trend).contains("monotonic trend"));
+    }
+
+    #[test]
+    fn dp_objective_is_the_iv_of_the_bins() {
+        // No missing rows, so the DP totals and the bin totals coincide.
+        let (x, y) = scores(5000, 3);
+        let config = NumericalConfig::new(6, 0.05, 0.5)
+            .unwrap()
+            .with_regularization(Regularization {
+                family: PenaltyFamily::None,
+                strength: 0.0,
+                target_bin_pct: None,
+            })
+            .unwrap();
+        let model = fit(config, &x, &y);
+        let score = model.score.unwrap();
+        let bins_iv: f64 = model.bins.iter().map(|b| b.iv).sum();
+        assert!(bins_iv > 0.0);
+        assert!(
+            (score.iv - bins_iv).abs() < 1e-9,
+            "{} vs {}",
+            score.iv,
+            bins_iv
+        );
+        assert_eq!(score.penalized, score.iv);
+    }
+
+    #[test]
+    fn special_rows_stay_out_of_the_dp() {
+        let (x, y) = scores(2000, 3);
+        let config = NumericalConfig::new(6, 0.05, 0.3).unwrap();
+        let plain = fit(config.clone(), &x, &y);
+
+        // A third of the rows, all events, on the special code: counted in
+        // the totals they would loosen the size bounds and move every WoE.
+        let mut xs = x.to_vec();
+        let mut ys = y.to_vec();
+        xs.extend([-999.0; 1000]);
+        ys.extend([1; 1000]);
+        let config = config
+            .with_special_codes(SpecialGroup::from_values(&[-999.0]))
+            .unwrap();
+        let special = fit(config, &Array1::from(xs), &Array1::from(ys));
+
+        let regular = |m: &NumericalModel| -> Vec<((f64, f64), i32, i32)> {
+            m.bins
+                .iter()
+                .filter(|b| !b.is_missing && !b.is_special())
+                .map(|b| (b.range, b.pos, b.neg))
+                .collect()
+        };
+        assert_eq!(regular(&special), regular(&plain));
+        let special_bin = special.bins.iter().find(|b| b.is_special()).unwrap();
+        assert_eq!((special_bin.pos, special_bin.neg), (1000, 0));
+    }
+
+    #[test]
+    fn invalid_inputs_are_rejected() {
+        let binning = NumericalBinning::new(NumericalConfig::new(6, 0.05, 0.3).unwrap());
+        let (mut x, mut y) = scores(1000, 3);
+        x[10] = f64::INFINITY;
+        assert_eq!(
+            binning.fit(x.view(), y.view()).unwrap_err(),
+            BinningError::InfiniteValue
+        );
+        x[10] = 5.0;
+        y[20] = 2;
+        assert_eq!(
+            binning.fit(x.view(), y.view()).unwrap_err(),
+            BinningError::NonBinaryTarget(2)
+        );
+    }
+
+    #[test]
+    fn from_json_validates_config() {
+        let (x, y) = scores(1000, 3);
+        let model = fit(NumericalConfig::new(6, 0.05, 0.3).unwrap(), &x, &y);
+        let mut value: serde_json::Value = serde_json::from_str(&model.to_json().unwrap()).unwrap();
+        value["config"]["prebinning"] = serde_json::json!({"method": "uniform", "n_bins": 0});
+        assert!(matches!(
+            NumericalModel::from_json(&value.to_string()),
+            Err(BinningError::InvalidConfig(_))
+        ));
+        value["config"]["prebinning"] = serde_json::json!({"method": "uniform", "n_bins": 10});
+        value["config"]["max_bins"] = serde_json::json!(0);
+        assert!(matches!(
+            NumericalModel::from_json(&value.to_string()),
+            Err(BinningError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn merge_bins_counts_match_recount() {
+        let (x, y) = scores(5000, 7);
+        let mut model = fit(NumericalConfig::new(6, 0.05, 0.3).unwrap(), &x, &y);
+        let n_bins = model.bins.len();
+        model.merge_bins(0).unwrap();
+        assert_eq!(model.bins.len(), n_bins - 1);
+        assert_counts_match(&model, &x, &y);
+    }
+
+    #[test]
+    fn loaded_models_can_be_edited() {
+        let (x, y) = scores(5000, 7);
+        let mut model = fit(NumericalConfig::new(6, 0.05, 0.3).unwrap(), &x, &y);
+        let mut loaded = NumericalModel::from_json(&model.to_json().unwrap()).unwrap();
+        model.merge_bins(0).unwrap();
+        loaded.merge_bins(0).unwrap();
+        assert_eq!(loaded.to_json().unwrap(), model.to_json().unwrap());
+        assert_counts_match(&loaded, &x, &y);
+    }
+
+    #[test]
+    fn event_limits_bound_every_bin_or_fail() {
+        let (x, y) = scores(3000, 5);
+        let n_events = y.iter().filter(|&&v| v == 1).count() as i32;
+        let limits = EventLimits {
+            min_bin_n_event: Some(40),
+            min_bin_n_nonevent: Some(40),
+            ..EventLimits::default()
+        };
+        let config = NumericalConfig::new(8, 0.02, 0.5)
+            .unwrap()
+            .with_event_limits(limits)
+            .unwrap();
+        let model = fit(config.clone(), &x, &y);
+        assert!(
+            model
+                .bins
+                .iter()
+                .filter(|b| !b.is_missing && !b.is_special())
+                .count()
+                > 1
+        );
+        for bin in model
+            .bins
+            .iter()
+            .filter(|b| !b.is_missing && !b.is_special())
+        {
+            assert!(bin.pos >= 40 && bin.neg >= 40, "{:?}", bin);
+        }
+
+        let too_many = EventLimits {
+            min_bin_n_event: Some(n_events + 1),
+            ..EventLimits::default()
+        };
+        let err = NumericalBinning::new(config.clone().with_event_limits(too_many).unwrap())
+            .fit(x.view(), y.view())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            BinningError::InvalidInput(format!(
+                "min_bin_n_event ({}) exceeds the {} events in the data",
+                n_events + 1,
+                n_events
+            ))
+        );
+
+        // Eight bins of at most one event each cannot hold every event.
+        let too_few = EventLimits {
+            max_bin_n_event: Some(1),
+            ..EventLimits::default()
+        };
+        let err = NumericalBinning::new(config.with_event_limits(too_few).unwrap())
+            .fit(x.view(), y.view())
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("No binning satisfies the event count limits"),
+            "{}",
+            err
+        );
+    }
+
+    /// Smallest WoE and event rate steps between adjacent regular bins.
+    fn min_steps(model: &NumericalModel) -> (f64, f64) {
+        let regular: Vec<&NumBin> = model
+            .bins
+            .iter()
+            .filter(|b| !b.is_missing && !b.is_special())
+            .collect();
+        regular
+            .windows(2)
+            .fold((f64::INFINITY, f64::INFINITY), |(woe, rate), w| {
+                (
+                    woe.min((w[1].woe - w[0].woe).abs()),
+                    rate.min((w[1].event_rate() - w[0].event_rate()).abs()),
+                )
+            })
+    }
+
+    #[test]
+    fn adjacent_bins_keep_the_minimum_separation() {
+        let (x, y) = scores(5000, 9);
+        let config = NumericalConfig::new(12, 0.02, 0.5).unwrap();
+        let (woe_step, rate_step) = min_steps(&fit(config.clone(), &x, &y));
+        let separation = Separation {
+            min_woe_diff: Some(2.0 * woe_step + 0.05),
+            min_event_rate_diff: Some(2.0 * rate_step + 0.01),
+        };
+        let model = fit(config.with_separation(separation).unwrap(), &x, &y);
+        assert!(
+            model
+                .bins
+                .iter()
+                .filter(|b| !b.is_missing && !b.is_special())
+                .count()
+                > 1
+        );
+        let (woe_step, rate_step) = min_steps(&model);
+        assert!(woe_step >= separation.min_woe_diff.unwrap());
+        assert!(rate_step >= separation.min_event_rate_diff.unwrap());
+    }
+
+    #[test]
+    fn development_sample_is_stable() {
+        let (x, y) = scores(3000, 11);
+        let model = fit(NumericalConfig::new(6, 0.05, 0.3).unwrap(), &x, &y);
+        let stability = model.stability(x.view(), Some(y.view())).unwrap();
+        assert!(stability.psi.abs() < 1e-12);
+        assert_eq!(stability.n_unbinned, 0);
+        for bin in &stability.bins {
+            let drift = bin.drift.unwrap();
+            assert!(drift.woe_drift.unwrap().abs() < 1e-9);
+            assert!(drift.iv_drift.unwrap().abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn stability_checks_x_the_same_with_or_without_y() {
+        let (x, y) = scores(3000, 11);
+        let model = fit(NumericalConfig::new(6, 0.05, 0.3).unwrap(), &x, &y);
+
+        // A sample gone all missing is what monitoring has to report.
+        let missing = Array1::from(vec![f64::NAN; 50]);
+        let without = model.stability(missing.view(), None).unwrap();
+        let with = model
+            .stability(missing.view(), Some(Array1::from(vec![1; 50]).view()))
+            .unwrap();
+        assert!(without.psi > 1.0);
+        assert_eq!(
+            (with.psi, with.n_unbinned),
+            (without.psi, without.n_unbinned)
+        );
+
+        let empty = Array1::<f64>::zeros(0);
+        let infinite = Array1::from(vec![1.0, f64::INFINITY]);
+        for (x_new, y_new, expected) in [
+            (&empty, None, BinningError::EmptyInput),
+            (&empty, Some(Array1::zeros(0)), BinningError::EmptyInput),
+            (&infinite, None, BinningError::InfiniteValue),
+            (
+                &infinite,
+                Some(Array1::from(vec![0, 1])),
+                BinningError::InfiniteValue,
+            ),
+        ] {
+            assert_eq!(
+                model
+                    .stability(x_new.view(), y_new.as_ref().map(|y| y.view()))
+                    .unwrap_err(),
+                expected
+            );
+        }
+        let x_new = Array1::from(vec![1.0, 2.0]);
+        assert_eq!(
+            model
+                .stability(x_new.view(), Some(Array1::from(vec![0, 2]).view()))
+                .unwrap_err(),
+            BinningError::NonBinaryTarget(2)
+        );
+        assert!(matches!(
+            model.stability(x_new.view(), Some(Array1::from(vec![0]).view())),
+            Err(BinningError::LengthMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn single_class_targets_leave_woe_undefined() {
+        let (x, y) = scores(3000, 11);
+        let model = fit(NumericalConfig::new(6, 0.05, 0.3).unwrap(), &x, &y);
+        let no_events = Array1::from(vec![0; x.len()]);
+        let stability = model.stability(x.view(), Some(no_events.view())).unwrap();
+        assert_eq!(stability.psi, model.stability(x.view(), None).unwrap().psi);
+        for bin in &stability.bins {
+            let drift = bin.drift.unwrap();
+            assert_eq!(drift.event_rate, 0.0);
+            assert_eq!((drift.woe, drift.iv), (None, None));
+            assert_eq!((drift.woe_drift, drift.iv_drift), (None, None));
+        }
+    }
+}
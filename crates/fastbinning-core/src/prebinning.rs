@@ -0,0 +1,364 @@
+use crate::config::{PrebinMethod, Prebinning};
+use crate::significance::chi2_statistic;
+
+/// A row of the sorted, non-missing data handed to a prebinning strategy.
+pub(crate) trait SortedRow: Copy {
+    fn value(&self) -> f64;
+    /// `(pos, neg)` weight the row contributes.
+    fn class_weights(&self) -> (f64, f64);
+}
+
+impl SortedRow for (f64, i32) {
+    #[inline]
+    fn value(&self) -> f64 {
+        self.0
+    }
+
+    #[inline]
+    fn class_weights(&self) -> (f64, f64) {
+        if self.1 == 1 { (1.0, 0.0) } else { (0.0, 1.0) }
+    }
+}
+
+impl SortedRow for (f64, i32, f64) {
+    #[inline]
+    fn value(&self) -> f64 {
+        self.0
+    }
+
+    #[inline]
+    fn class_weights(&self) -> (f64, f64) {
+        if self.1 == 1 {
+            (self.2, 0.0)
+        } else {
+            (0.0, self.2)
+        }
+    }
+}
+
+/// Rows of a real-valued target carry no class weights, so only the
+/// unsupervised methods apply to them.
+impl SortedRow for (f64, f64) {
+    #[inline]
+    fn value(&self) -> f64 {
+        self.0
+    }
+
+    #[inline]
+    fn class_weights(&self) -> (f64, f64) {
+        (0.0, 0.0)
+    }
+}
+
+/// Rows of a multiclass target, by class index; as for real-valued targets,
+/// only the unsupervised methods apply.
+impl SortedRow for (f64, usize) {
+    #[inline]
+    fn value(&self) -> f64 {
+        self.0
+    }
+
+    #[inline]
+    fn class_weights(&self) -> (f64, f64) {
+        (0.0, 0.0)
+    }
+}
+
+/// Rows sharing one distinct value; prebins never split an atom.
+struct Atom {
+    end: usize,
+    value: f64,
+    count: usize,
+    pos: f64,
+    neg: f64,
+}
+
+/// Default prebin count: `sqrt(n)` clamped to 100..500.
+pub(crate) fn default_prebins(n: usize) -> usize {
+    ((n as f64).sqrt() as usize).clamp(100, 500)
+}
+
+/// Index of the last row of each prebin over `rows`, sorted by value.
+pub(crate) fn prebin_ends<R: SortedRow>(prebinning: &Prebinning, rows: &[R]) -> Vec<usize> {
+    if rows.is_empty() {
+        return Vec::new();
+    }
+    let atoms = atoms(rows);
+    let n_bins = prebinning
+        .n_bins
+        .unwrap_or_else(|| default_prebins(rows.len()));
+    let atom_ends = match prebinning.method {
+        PrebinMethod::Quantile => quantile(&atoms, rows.len(), n_bins),
+        PrebinMethod::Uniform => uniform(&atoms, n_bins),
+        PrebinMethod::CartGini => tree(&atoms, n_bins, gini, None),
+        PrebinMethod::CartEntropy => tree(&atoms, n_bins, entropy, None),
+        PrebinMethod::Mdlp => {
+            let max_leaves = prebinning.n_bins.unwrap_or(atoms.len());
+            tree(&atoms, max_leaves, entropy, Some(mdlp_accepts))
+        }
+        PrebinMethod::ChiMerge => {
+            let start = quantile(&atoms, rows.len(), default_prebins(rows.len()));
+            chi_merge(&atoms, start, prebinning.n_bins)
+        }
+    };
+    atom_ends.into_iter().map(|a| atoms[a].end).collect()
+}
+
+/// Adds a prebin end at the last row `<= cut` for each of `cuts`, so that no
+/// prebin straddles a cut point.
+pub(crate) fn with_cuts<R: SortedRow>(
+    mut ends: Vec<usize>,
+    rows: &[R],
+    cuts: &[f64],
+) -> Vec<usize> {
+    for &cut in cuts {
+        let split = rows.partition_point(|r| r.value() <= cut);
+        if split > 0 && split < rows.len() {
+            ends.push(split - 1);
+        }
+    }
+    ends.sort_unstable();
+    ends.dedup();
+    ends
+}
+
+/// Upper edge of the prebin ending at row `end`: the cut point it was closed
+/// at, if any, so that bin ranges match the cut points exactly.
+pub(crate) fn edge<R: SortedRow>(rows: &[R], end: usize, cuts: &[f64]) -> f64 {
+    let value = rows[end].value();
+    let next = cuts.partition_point(|&c| c < value);
+    match (cuts.get(next), rows.get(end + 1)) {
+        (Some(&cut), Some(row)) if cut < row.value() => cut,
+        _ => value,
+    }
+}
+
+fn atoms<R: SortedRow>(rows: &[R]) -> Vec<Atom> {
+    let mut atoms: Vec<Atom> = Vec::new();
+    for (i, row) in rows.iter().enumerate() {
+        let (p, n) = row.class_weights();
+        match atoms.last_mut() {
+            Some(atom) if atom.value == row.value() => {
+                atom.end = i;
+                atom.count += 1;
+                atom.pos += p;
+                atom.neg += n;
+            }
+            _ => atoms.push(Atom {
+                end: i,
+                value: row.value(),
+                count: 1,
+                pos: p,
+                neg: n,
+            }),
+        }
+    }
+    atoms
+}
+
+/// Chunks of about `n / n_bins` rows.
+fn quantile(atoms: &[Atom], n: usize, n_bins: usize) -> Vec<usize> {
+    let chunk_size = (n as f64 / n_bins as f64).ceil() as usize;
+    let mut ends = Vec::new();
+    let mut curr_count = 0;
+    for (a, atom) in atoms.iter().enumerate() {
+        curr_count += atom.count;
+        if a == atoms.len() - 1 || curr_count >= chunk_size {
+            ends.push(a);
+            curr_count = 0;
+        }
+    }
+    ends
+}
+
+/// `n_bins` equal-width intervals over the value range; empty ones are dropped.
+fn uniform(atoms: &[Atom], n_bins: usize) -> Vec<usize> {
+    let min = atoms[0].value;
+    let width = (atoms[atoms.len() - 1].value - min) / n_bins as f64;
+    let bucket = |v: f64| {
+        if width > 0.0 {
+            (((v - min) / width) as usize).min(n_bins - 1)
+        } else {
+            0
+        }
+    };
+    (0..atoms.len())
+        .filter(|&a| a == atoms.len() - 1 || bucket(atoms[a].value) != bucket(atoms[a + 1].value))
+        .collect()
+}
+
+fn gini(pos: f64, neg: f64) -> f64 {
+    let total = pos + neg;
+    if total <= 0.0 {
+        return 0.0;
+    }
+    let p = pos / total;
+    2.0 * p * (1.0 - p)
+}
+
+/// Binary entropy in bits.
+fn entropy(pos: f64, neg: f64) -> f64 {
+    let total = pos + neg;
+    let h = |c: f64| {
+        if c > 0.0 {
+            let p = c / total;
+            -p * p.log2()
+        } else {
+            0.0
+        }
+    };
+    h(pos) + h(neg)
+}
+
+/// Candidate split of the atoms `start..=end`, after atom `at`.
+struct Split {
+    start: usize,
+    end: usize,
+    at: usize,
+    gain: f64,
+}
+
+/// Best-first binary splitting that maximizes the weighted impurity decrease,
+/// stopping at `max_leaves` or when `accepts` rejects every remaining split.
+fn tree(
+    atoms: &[Atom],
+    max_leaves: usize,
+    impurity: fn(f64, f64) -> f64,
+    accepts: Option<fn(&[Atom], &Split) -> bool>,
+) -> Vec<usize> {
+    let mut cum = Vec::with_capacity(atoms.len());
+    let (mut p_acc, mut n_acc) = (0.0, 0.0);
+    for atom in atoms {
+        p_acc += atom.pos;
+        n_acc += atom.neg;
+        cum.push((p_acc, n_acc));
+    }
+    let weights = |i: usize, j: usize| {
+        if i == 0 {
+            cum[j]
+        } else {
+            (cum[j].0 - cum[i - 1].0, cum[j].1 - cum[i - 1].1)
+        }
+    };
+    let best_split = |start: usize, end: usize| -> Option<Split> {
+        let (p, n) = weights(start, end);
+        let parent = (p + n) * impurity(p, n);
+        let mut best: Option<Split> = None;
+        for at in start..end {
+            let (lp, ln) = weights(start, at);
+            let (rp, rn) = (p - lp, n - ln);
+            let gain = parent - (lp + ln) * impurity(lp, ln) - (rp + rn) * impurity(rp, rn);
+            if gain > best.as_ref().map_or(f64::EPSILON, |b| b.gain) {
+                best = Some(Split {
+                    start,
+                    end,
+                    at,
+                    gain,
+                });
+            }
+        }
+        best.filter(|split| accepts.is_none_or(|accepts| accepts(atoms, split)))
+    };
+
+    let mut ends = vec![atoms.len() - 1];
+    let mut frontier: Vec<Split> = best_split(0, atoms.len() - 1).into_iter().collect();
+    while ends.len() < max_leaves && !frontier.is_empty() {
+        let best = (0..frontier.len())
+            .max_by(|&a, &b| frontier[a].gain.total_cmp(&frontier[b].gain))
+            .unwrap_or(0);
+        let split = frontier.swap_remove(best);
+        ends.push(split.at);
+        frontier.extend(best_split(split.start, split.at));
+        frontier.extend(best_split(split.at + 1, split.end));
+    }
+    ends.sort_unstable();
+    ends
+}
+
+/// Fayyad-Irani MDL stopping rule on the entropy gain of `split`.
+fn mdlp_accepts(atoms: &[Atom], split: &Split) -> bool {
+    let tally = |range: &[Atom]| {
+        range.iter().fold((0, 0.0, 0.0), |(c, p, n), a| {
+            (c + a.count, p + a.pos, n + a.neg)
+        })
+    };
+    let n_classes = |p: f64, n: f64| (p > 0.0) as i32 + (n > 0.0) as i32;
+    let (count, p, n) = tally(&atoms[split.start..=split.end]);
+    let (_, lp, ln) = tally(&atoms[split.start..=split.at]);
+    let (rp, rn) = (p - lp, n - ln);
+    let (ent, ent_l, ent_r) = (entropy(p, n), entropy(lp, ln), entropy(rp, rn));
+    let (k, k_l, k_r) = (n_classes(p, n), n_classes(lp, ln), n_classes(rp, rn));
+
+    let count = count as f64;
+    let gain = split.gain / (p + n);
+    let delta =
+        (3f64.powi(k) - 2.0).log2() - (k as f64 * ent - k_l as f64 * ent_l - k_r as f64 * ent_r);
+    gain > ((count - 1.0).log2() + delta) / count
+}
+
+/// Chi-square critical value at 5% for one degree of freedom.
+const CHI2_CRITICAL: f64 = 3.841;
+
+/// Merges adjacent prebins with the lowest chi-square statistic while it is
+/// below `CHI2_CRITICAL`, or while there are more than `max_bins`. As in
+/// Kerber's ChiMerge, the statistic is not continuity-corrected.
+fn chi_merge(atoms: &[Atom], mut ends: Vec<usize>, max_bins: Option<usize>) -> Vec<usize> {
+    let mut counts: Vec<(f64, f64)> = Vec::with_capacity(ends.len());
+    let mut start = 0;
+    for &end in &ends {
+        counts.push(
+            atoms[start..=end]
+                .iter()
+                .fold((0.0, 0.0), |(p, n), a| (p + a.pos, n + a.neg)),
+        );
+        start = end + 1;
+    }
+
+    while ends.len() > 1 {
+        let (i, chi2) = (0..ends.len() - 1)
+            .map(|i| (i, chi2_statistic(counts[i], counts[i + 1], false)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or((0, f64::INFINITY));
+        if chi2 >= CHI2_CRITICAL && max_bins.is_none_or(|max| ends.len() <= max) {
+            break;
+        }
+        let (p, n) = counts.remove(i + 1);
+        counts[i].0 += p;
+        counts[i].1 += n;
+        ends.remove(i);
+    }
+    ends
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_method_gives_ordered_ends_on_distinct_values() {
+        let rows: Vec<(f64, i32)> = (0..200)
+            .map(|i| ((i / 4) as f64, (i % 7 == 0 || i >= 150) as i32))
+            .collect();
+        for method in [
+            PrebinMethod::Quantile,
+            PrebinMethod::Uniform,
+            PrebinMethod::CartGini,
+            PrebinMethod::CartEntropy,
+            PrebinMethod::Mdlp,
+            PrebinMethod::ChiMerge,
+        ] {
+            let prebinning = Prebinning {
+                method,
+                n_bins: Some(10),
+            };
+            let ends = prebin_ends(&prebinning, &rows);
+            assert!(!ends.is_empty() && ends.len() <= 10, "{:?}", method);
+            assert_eq!(*ends.last().unwrap(), rows.len() - 1, "{:?}", method);
+            assert!(ends.windows(2).all(|w| w[0] < w[1]), "{:?}", method);
+            // No prebin splits rows that share a value.
+            for &end in &ends[..ends.len() - 1] {
+                assert_ne!(rows[end].0, rows[end + 1].0, "{:?}", method);
+            }
+        }
+    }
+}
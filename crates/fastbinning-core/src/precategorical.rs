@@ -0,0 +1,138 @@
+use crate::special::SpecialTally;
+use crate::weights::BinWeights;
+use crate::woeiv::{Divergence, calc_divergence, calc_woe_iv};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreCatBinStats {
+    pub cum_pos: Vec<i32>,
+    pub cum_neg: Vec<i32>,
+    pub cum_wpos: Vec<f64>,
+    pub cum_wneg: Vec<f64>,
+    pub indices: Vec<i32>,
+    pub total_pos: i32,
+    pub total_neg: i32,
+    pub total_wpos: f64,
+    pub total_wneg: f64,
+    pub missing_pos: i32,
+    pub missing_neg: i32,
+    pub missing_weights: BinWeights,
+    pub special: Vec<SpecialTally>,
+    pub divergence: Divergence,
+    pub is_weighted: bool,
+}
+
+impl PreCatBinStats {
+    pub fn new(
+        pos: &[i32],
+        neg: &[i32],
+        indices: Vec<i32>,
+        missing_pos: i32,
+        missing_neg: i32,
+    ) -> Self {
+        let mut cum_pos = Vec::with_capacity(pos.len());
+        let mut cum_neg = Vec::with_capacity(neg.len());
+        let (mut p_acc, mut n_acc) = (0, 0);
+
+        for (&p, &n) in pos.iter().zip(neg.iter()) {
+            p_acc += p;
+            n_acc += n;
+            cum_pos.push(p_acc);
+            cum_neg.push(n_acc);
+        }
+
+        let cum_wpos = cum_pos.iter().map(|&p| p as f64).collect();
+        let cum_wneg = cum_neg.iter().map(|&n| n as f64).collect();
+        Self {
+            cum_pos,
+            cum_neg,
+            cum_wpos,
+            cum_wneg,
+            indices,
+            total_pos: p_acc,
+            total_neg: n_acc,
+            total_wpos: p_acc as f64,
+            total_wneg: n_acc as f64,
+            missing_pos,
+            missing_neg,
+            missing_weights: BinWeights {
+                pos: missing_pos as f64,
+                neg: missing_neg as f64,
+            },
+            special: Vec::new(),
+            divergence: Divergence::default(),
+            is_weighted: false,
+        }
+    }
+
+    pub fn with_weights(mut self, wpos: &[f64], wneg: &[f64], missing_weights: BinWeights) -> Self {
+        let (mut p_acc, mut n_acc) = (0.0, 0.0);
+        self.cum_wpos.clear();
+        self.cum_wneg.clear();
+
+        for (&p, &n) in wpos.iter().zip(wneg.iter()) {
+            p_acc += p;
+            n_acc += n;
+            self.cum_wpos.push(p_acc);
+            self.cum_wneg.push(n_acc);
+        }
+
+        self.total_wpos = p_acc;
+        self.total_wneg = n_acc;
+        self.missing_weights = missing_weights;
+        self.is_weighted = true;
+        self
+    }
+
+    pub fn with_special(mut self, special: Vec<SpecialTally>) -> Self {
+        self.special = special;
+        self
+    }
+
+    pub fn with_divergence(mut self, divergence: Divergence) -> Self {
+        self.divergence = divergence;
+        self
+    }
+
+    #[inline]
+    pub fn get_counts(&self, i: usize, j: usize) -> (i32, i32) {
+        let pos = if i == 0 {
+            self.cum_pos[j]
+        } else {
+            self.cum_pos[j] - self.cum_pos[i - 1]
+        };
+        let neg = if i == 0 {
+            self.cum_neg[j]
+        } else {
+            self.cum_neg[j] - self.cum_neg[i - 1]
+        };
+        (pos, neg)
+    }
+
+    #[inline]
+    pub fn get_weights(&self, i: usize, j: usize) -> (f64, f64) {
+        let pos = if i == 0 {
+            self.cum_wpos[j]
+        } else {
+            self.cum_wpos[j] - self.cum_wpos[i - 1]
+        };
+        let neg = if i == 0 {
+            self.cum_wneg[j]
+        } else {
+            self.cum_wneg[j] - self.cum_wneg[i - 1]
+        };
+        (pos, neg)
+    }
+
+    /// The configured divergence of prebins `i..=j`; IV by default.
+    #[inline]
+    pub fn calc_iv_range(&self, i: usize, j: usize) -> f64 {
+        let (pos, neg) = self.get_weights(i, j);
+        calc_divergence(self.divergence, pos, neg, self.total_wpos, self.total_wneg)
+    }
+
+    #[inline]
+    pub fn calc_woe_single(&self, pos: f64, neg: f64) -> f64 {
+        calc_woe_iv(pos, neg, self.total_wpos, self.total_wneg).0
+    }
+}
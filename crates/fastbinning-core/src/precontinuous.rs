@@ -0,0 +1,148 @@
+#[derive(Debug, Clone, Copy)]
+pub struct TargetSummary {
+    pub count: i32,
+    pub sum: f64,
+    pub sum_sq: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Default for TargetSummary {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            sum: 0.0,
+            sum_sq: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl TargetSummary {
+    #[inline]
+    pub fn push(&mut self, y: f64) {
+        self.count += 1;
+        self.sum += y;
+        self.sum_sq += y * y;
+        self.min = self.min.min(y);
+        self.max = self.max.max(y);
+    }
+
+    #[inline]
+    pub fn merge(&mut self, other: &TargetSummary) {
+        self.count += other.count;
+        self.sum += other.sum;
+        self.sum_sq += other.sum_sq;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        if self.count > 0 {
+            self.sum / self.count as f64
+        } else {
+            0.0
+        }
+    }
+
+    #[inline]
+    pub fn std(&self) -> f64 {
+        if self.count > 0 {
+            let mean = self.mean();
+            (self.sum_sq / self.count as f64 - mean * mean)
+                .max(0.0)
+                .sqrt()
+        } else {
+            0.0
+        }
+    }
+}
+
+pub struct PreContBinStats {
+    pub cum_count: Vec<i32>,
+    pub cum_sum: Vec<f64>,
+    pub prebins: Vec<TargetSummary>,
+    pub edges: Vec<f64>,
+    pub total_count: i32,
+    pub total_sum: f64,
+    /// Total sum of squares of the non-missing target around its mean.
+    pub total_ss: f64,
+    pub missing: TargetSummary,
+}
+
+impl PreContBinStats {
+    pub fn new(prebins: Vec<TargetSummary>, edges: Vec<f64>, missing: TargetSummary) -> Self {
+        let mut cum_count = Vec::with_capacity(prebins.len());
+        let mut cum_sum = Vec::with_capacity(prebins.len());
+        let (mut c_acc, mut s_acc, mut sq_acc) = (0, 0.0, 0.0);
+
+        for p in prebins.iter() {
+            c_acc += p.count;
+            s_acc += p.sum;
+            sq_acc += p.sum_sq;
+            cum_count.push(c_acc);
+            cum_sum.push(s_acc);
+        }
+        let total_ss = if c_acc > 0 {
+            (sq_acc - s_acc * s_acc / c_acc as f64).max(0.0)
+        } else {
+            0.0
+        };
+
+        Self {
+            cum_count,
+            cum_sum,
+            prebins,
+            edges,
+            total_count: c_acc,
+            total_sum: s_acc,
+            total_ss,
+            missing,
+        }
+    }
+
+    #[inline]
+    pub fn get_count_sum(&self, i: usize, j: usize) -> (i32, f64) {
+        if i == 0 {
+            (self.cum_count[j], self.cum_sum[j])
+        } else {
+            (
+                self.cum_count[j] - self.cum_count[i - 1],
+                self.cum_sum[j] - self.cum_sum[i - 1],
+            )
+        }
+    }
+
+    /// Between-group sum of squares contributed by the range, i.e. the
+    /// variance it explains relative to the grand mean.
+    #[inline]
+    pub fn calc_ss_range(&self, i: usize, j: usize) -> f64 {
+        let (count, sum) = self.get_count_sum(i, j);
+        if count == 0 {
+            return 0.0;
+        }
+        let diff = sum / count as f64 - self.total_sum / self.total_count as f64;
+        count as f64 * diff * diff
+    }
+
+    /// Share of the total sum of squares the range explains, so that scores
+    /// are on a fixed 0..1 scale whatever the units of the target.
+    #[inline]
+    pub fn calc_r2_range(&self, i: usize, j: usize) -> f64 {
+        if self.total_ss > 0.0 {
+            self.calc_ss_range(i, j) / self.total_ss
+        } else {
+            0.0
+        }
+    }
+
+    pub fn summarize_range(&self, i: usize, j: usize) -> TargetSummary {
+        let mut summary = TargetSummary::default();
+        for p in &self.prebins[i..=j] {
+            summary.merge(p);
+        }
+        summary
+    }
+}
@@ -0,0 +1,102 @@
+use crate::woeiv::calc_woe_iv;
+
+/// K-class generalization of `PreNumBinStats`: `cum_counts` is laid out
+/// row-major as `n_prebins x n_classes`.
+pub struct PreMultiBinStats {
+    pub cum_counts: Vec<i32>,
+    /// Running row count over all classes, one entry per prebin.
+    pub cum_total: Vec<i32>,
+    pub edges: Vec<f64>,
+    pub n_classes: usize,
+    pub totals: Vec<i32>,
+    pub missing: Vec<i32>,
+}
+
+impl PreMultiBinStats {
+    pub fn new(counts: &[i32], n_classes: usize, edges: Vec<f64>, missing: Vec<i32>) -> Self {
+        let mut cum_counts = Vec::with_capacity(counts.len());
+        let mut cum_total = Vec::with_capacity(edges.len());
+        let mut acc = vec![0; n_classes];
+        let mut total_acc = 0;
+
+        for row in counts.chunks(n_classes) {
+            for (a, &c) in acc.iter_mut().zip(row.iter()) {
+                *a += c;
+                total_acc += c;
+            }
+            cum_counts.extend_from_slice(&acc);
+            cum_total.push(total_acc);
+        }
+
+        Self {
+            cum_counts,
+            cum_total,
+            edges,
+            n_classes,
+            totals: acc,
+            missing,
+        }
+    }
+
+    #[inline]
+    pub fn get_count(&self, i: usize, j: usize) -> i32 {
+        if i == 0 {
+            self.cum_total[j]
+        } else {
+            self.cum_total[j] - self.cum_total[i - 1]
+        }
+    }
+
+    /// Writes the per-class counts of the range into `out`, which must hold
+    /// `n_classes` entries.
+    #[inline]
+    pub fn get_counts_into(&self, i: usize, j: usize, out: &mut [i32]) {
+        let k = self.n_classes;
+        let end = &self.cum_counts[j * k..(j + 1) * k];
+        if i == 0 {
+            out.copy_from_slice(end);
+        } else {
+            let start = &self.cum_counts[(i - 1) * k..i * k];
+            for ((o, e), s) in out.iter_mut().zip(end.iter()).zip(start.iter()) {
+                *o = e - s;
+            }
+        }
+    }
+
+    pub fn get_counts(&self, i: usize, j: usize) -> Vec<i32> {
+        let mut counts = vec![0; self.n_classes];
+        self.get_counts_into(i, j, &mut counts);
+        counts
+    }
+
+    /// Sum of one-vs-rest IVs of the range: each class is treated as the
+    /// event and every other class as the non-event. `buf` is scratch space
+    /// of `n_classes` entries, so the DP does not allocate per range.
+    #[inline]
+    pub fn calc_divergence_range(&self, i: usize, j: usize, buf: &mut [i32]) -> f64 {
+        self.get_counts_into(i, j, buf);
+        calc_one_vs_rest_iv(buf, &self.totals)
+    }
+}
+
+pub fn calc_one_vs_rest(counts: &[i32], totals: &[i32]) -> Vec<(f64, f64)> {
+    let count: i32 = counts.iter().sum();
+    let total: i32 = totals.iter().sum();
+    counts
+        .iter()
+        .zip(totals.iter())
+        .map(|(&c, &t)| calc_woe_iv(c as f64, (count - c) as f64, t as f64, (total - t) as f64))
+        .collect()
+}
+
+/// Sum of the IVs of `calc_one_vs_rest`, without collecting them.
+#[inline]
+pub fn calc_one_vs_rest_iv(counts: &[i32], totals: &[i32]) -> f64 {
+    let count: i32 = counts.iter().sum();
+    let total: i32 = totals.iter().sum();
+    counts
+        .iter()
+        .zip(totals.iter())
+        .map(|(&c, &t)| calc_woe_iv(c as f64, (count - c) as f64, t as f64, (total - t) as f64).1)
+        .sum()
+}
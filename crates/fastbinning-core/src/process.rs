@@ -0,0 +1,335 @@
+use crate::categorical::{CategoricalBinning, CategoricalModel};
+use crate::config::{CategoricalConfig, NumericalConfig};
+use crate::error::BinningError;
+use crate::json;
+use crate::numerical::{NumericalBinning, NumericalModel};
+use ndarray::{Array2, ArrayView1, ArrayView2};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VariableKind {
+    Numerical,
+    Categorical,
+}
+
+impl VariableKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VariableKind::Numerical => "numerical",
+            VariableKind::Categorical => "categorical",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum VariableConfig {
+    Numerical(NumericalConfig),
+    Categorical(CategoricalConfig),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "model", rename_all = "lowercase")]
+pub enum FittedVariable {
+    Numerical(NumericalModel),
+    Categorical(CategoricalModel),
+}
+
+impl FittedVariable {
+    pub fn kind(&self) -> VariableKind {
+        match self {
+            FittedVariable::Numerical(_) => VariableKind::Numerical,
+            FittedVariable::Categorical(_) => VariableKind::Categorical,
+        }
+    }
+
+    pub fn n_bins(&self) -> usize {
+        match self {
+            FittedVariable::Numerical(m) => m.bins.len(),
+            FittedVariable::Categorical(m) => m.bins.len(),
+        }
+    }
+
+    pub fn total_iv(&self) -> f64 {
+        match self {
+            FittedVariable::Numerical(m) => m.total_iv(),
+            FittedVariable::Categorical(m) => m.total_iv(),
+        }
+    }
+
+    pub fn config(&self) -> VariableConfig {
+        match self {
+            FittedVariable::Numerical(m) => VariableConfig::Numerical(m.config.clone()),
+            FittedVariable::Categorical(m) => VariableConfig::Categorical(m.config.clone()),
+        }
+    }
+
+    fn transform(&self, x: ArrayView1<f64>) -> Result<Vec<f64>, BinningError> {
+        match self {
+            FittedVariable::Numerical(m) => Ok(m.transform(x)),
+            FittedVariable::Categorical(m) => {
+                m.transform(&to_categories(x, m.config.missing_code)?)
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), BinningError> {
+        match self {
+            FittedVariable::Numerical(m) => m.config.validate(),
+            FittedVariable::Categorical(m) => m.config.validate(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VariableSummary {
+    pub index: usize,
+    pub kind: VariableKind,
+    pub n_bins: usize,
+    pub iv: f64,
+}
+
+/// Categorical columns arrive as floats in the shared matrix; NaN maps to the
+/// missing category code and every other value must be an integral `i32`.
+fn to_categories(x: ArrayView1<f64>, missing_code: i32) -> Result<Vec<i32>, BinningError> {
+    x.iter()
+        .map(|&v| {
+            if v.is_nan() {
+                Ok(missing_code)
+            } else if v.fract() == 0.0 && v >= i32::MIN as f64 && v <= i32::MAX as f64 {
+                Ok(v as i32)
+            } else {
+                Err(BinningError::InvalidInput(format!(
+                    "Categorical value {} is not an integer category code",
+                    v
+                )))
+            }
+        })
+        .collect()
+}
+
+fn check_columns(expected: usize, found: usize) -> Result<(), BinningError> {
+    if expected != found {
+        return Err(BinningError::InvalidInput(format!(
+            "x has {} columns but {} variable configs were given",
+            found, expected
+        )));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+pub struct BinningProcess {
+    pub configs: Vec<VariableConfig>,
+}
+
+impl BinningProcess {
+    pub fn new(configs: Vec<VariableConfig>) -> Self {
+        Self { configs }
+    }
+
+    pub fn fit(
+        &self,
+        x: ArrayView2<f64>,
+        y: ArrayView1<i32>,
+        sample_weight: Option<ArrayView1<f64>>,
+    ) -> Result<BinningProcessModel, BinningError> {
+        check_columns(self.configs.len(), x.ncols())?;
+        let y_vec = y.to_vec();
+        let w_vec = sample_weight.map(|w| w.to_vec());
+        let variables = self
+            .configs
+            .par_iter()
+            .enumerate()
+            .map(|(j, config)| {
+                let column = x.column(j);
+                let fitted = match config {
+                    VariableConfig::Numerical(c) => {
+                        let binning = NumericalBinning::new(c.clone());
+                        match sample_weight {
+                            Some(w) => binning.fit_weighted(column, y, w),
+                            None => binning.fit(column, y),
+                        }
+                        .map(FittedVariable::Numerical)
+                    }
+                    VariableConfig::Categorical(c) => {
+                        let binning = CategoricalBinning::new(c.clone());
+                        to_categories(column, c.missing_code)
+                            .and_then(|categories| match &w_vec {
+                                Some(w) => binning.fit_weighted(&categories, &y_vec, w),
+                                None => binning.fit(&categories, &y_vec),
+                            })
+                            .map(FittedVariable::Categorical)
+                    }
+                };
+                fitted.map_err(|e| BinningError::Variable {
+                    index: j,
+                    source: Box::new(e),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(BinningProcessModel { variables })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinningProcessModel {
+    pub variables: Vec<FittedVariable>,
+}
+
+impl BinningProcessModel {
+    pub fn transform(&self, x: ArrayView2<f64>) -> Result<Array2<f64>, BinningError> {
+        check_columns(self.variables.len(), x.ncols())?;
+        let columns: Vec<Vec<f64>> = self
+            .variables
+            .par_iter()
+            .enumerate()
+            .map(|(j, variable)| {
+                variable
+                    .transform(x.column(j))
+                    .map_err(|e| BinningError::Variable {
+                        index: j,
+                        source: Box::new(e),
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+
+        let mut output = Array2::<f64>::zeros((x.nrows(), columns.len()));
+        for (j, column) in columns.into_iter().enumerate() {
+            output
+                .column_mut(j)
+                .iter_mut()
+                .zip(column)
+                .for_each(|(o, v)| *o = v);
+        }
+        Ok(output)
+    }
+
+    pub fn to_json(&self) -> Result<String, BinningError> {
+        json::to_json("process", self)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, BinningError> {
+        let model: Self = json::from_json("process", json)?;
+        for (index, variable) in model.variables.iter().enumerate() {
+            variable.validate().map_err(|e| BinningError::Variable {
+                index,
+                source: Box::new(e),
+            })?;
+        }
+        Ok(model)
+    }
+
+    pub fn summary(&self) -> Vec<VariableSummary> {
+        self.variables
+            .iter()
+            .enumerate()
+            .map(|(index, v)| VariableSummary {
+                index,
+                kind: v.kind(),
+                n_bins: v.n_bins(),
+                iv: v.total_iv(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::{categories, scores};
+    use ndarray::Array1;
+
+    /// A score column next to a category column coded as floats, with the
+    /// categorical missing rows as NaN.
+    fn sample(n: usize) -> (Array2<f64>, Array1<i32>) {
+        let (scores, y) = scores(n, 3);
+        let (codes, _) = categories(n, 5);
+        let mut x = Array2::<f64>::zeros((n, 2));
+        for i in 0..n {
+            x[[i, 0]] = scores[i];
+            x[[i, 1]] = if codes[i] < 0 {
+                f64::NAN
+            } else {
+                codes[i] as f64
+            };
+        }
+        (x, y)
+    }
+
+    fn process() -> BinningProcess {
+        BinningProcess::new(vec![
+            VariableConfig::Numerical(NumericalConfig::new(5, 0.05, 0.5).unwrap()),
+            VariableConfig::Categorical(CategoricalConfig::new(4, 0.05, 0.5).unwrap()),
+        ])
+    }
+
+    #[test]
+    fn fit_matches_the_single_binners() {
+        let (x, y) = sample(2000);
+        let model = process().fit(x.view(), y.view(), None).unwrap();
+        let summary = model.summary();
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[0].kind, VariableKind::Numerical);
+        assert_eq!(summary[1].kind, VariableKind::Categorical);
+
+        let numerical = NumericalBinning::new(NumericalConfig::new(5, 0.05, 0.5).unwrap())
+            .fit(x.column(0), y.view())
+            .unwrap();
+        assert_eq!(summary[0].n_bins, numerical.bins.len());
+        assert_eq!(summary[0].iv, numerical.total_iv());
+
+        let output = model.transform(x.view()).unwrap();
+        assert_eq!(output.dim(), (2000, 2));
+        assert_eq!(output.column(0).to_vec(), numerical.transform(x.column(0)));
+    }
+
+    #[test]
+    fn non_integral_categories_are_rejected() {
+        let (mut x, y) = sample(500);
+        let model = process().fit(x.view(), y.view(), None).unwrap();
+        for bad in [2.5, f64::INFINITY, 1e12] {
+            x[[7, 1]] = bad;
+            for result in [
+                process().fit(x.view(), y.view(), None).map(|_| ()),
+                model.transform(x.view()).map(|_| ()),
+            ] {
+                match result {
+                    Err(BinningError::Variable { index: 1, source }) => {
+                        assert!(matches!(*source, BinningError::InvalidInput(_)))
+                    }
+                    other => panic!("expected an invalid category, got {:?}", other),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn column_count_must_match_configs() {
+        let (x, y) = sample(200);
+        let one = BinningProcess::new(vec![VariableConfig::Numerical(
+            NumericalConfig::new(5, 0.05, 0.5).unwrap(),
+        )]);
+        assert!(matches!(
+            one.fit(x.view(), y.view(), None),
+            Err(BinningError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn json_round_trip_keeps_variables_and_transform() {
+        let (x, y) = sample(1000);
+        let model = process().fit(x.view(), y.view(), None).unwrap();
+        let json = model.to_json().unwrap();
+        let loaded = BinningProcessModel::from_json(&json).unwrap();
+        assert_eq!(loaded.to_json().unwrap(), json);
+        assert_eq!(
+            loaded.transform(x.view()).unwrap(),
+            model.transform(x.view()).unwrap()
+        );
+        assert!(matches!(
+            crate::numerical::NumericalModel::from_json(&json),
+            Err(BinningError::Serialization(_))
+        ));
+    }
+}
@@ -0,0 +1,244 @@
+use crate::config::Significance;
+use crate::error::BinningError;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Test of whether two adjacent bins have different event rates.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PValueTest {
+    /// Pearson's chi-square test with Yates' continuity correction.
+    #[default]
+    Chi2,
+    /// Two-proportion z-test on the pooled event rate.
+    Z,
+    /// Fisher's exact test, for bins with few events.
+    Fisher,
+}
+
+impl FromStr for PValueTest {
+    type Err = BinningError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "chi2" => Ok(PValueTest::Chi2),
+            "z" => Ok(PValueTest::Z),
+            "fisher" => Ok(PValueTest::Fisher),
+            other => Err(BinningError::InvalidConfig(format!(
+                "Unknown pvalue test '{}', expected one of 'chi2', 'z', 'fisher'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Two-sided p-value of `test` on the `(pos, neg)` counts of two bins. Bins
+/// with no rows, or a pair with a single class, give 1.
+pub fn pvalue(test: PValueTest, a: (i32, i32), b: (i32, i32)) -> f64 {
+    let (n_a, n_b) = (a.0 + a.1, b.0 + b.1);
+    let (n_pos, n_neg) = (a.0 + b.0, a.1 + b.1);
+    if n_a == 0 || n_b == 0 || n_pos == 0 || n_neg == 0 {
+        return 1.0;
+    }
+    match test {
+        PValueTest::Chi2 => chi2(a, b),
+        PValueTest::Z => z_test(a, b),
+        PValueTest::Fisher => fisher(a, b),
+    }
+}
+
+fn chi2(a: (i32, i32), b: (i32, i32)) -> f64 {
+    let as_f64 = |(p, n): (i32, i32)| (p as f64, n as f64);
+    let stat = chi2_statistic(as_f64(a), as_f64(b), true);
+    erfc((stat / 2.0).sqrt())
+}
+
+/// Pearson's chi-square statistic of the 2x2 table of the `(pos, neg)`
+/// weights of two bins, with Yates' continuity correction when `yates` is
+/// set. Cells with no expected weight are skipped.
+pub(crate) fn chi2_statistic(a: (f64, f64), b: (f64, f64), yates: bool) -> f64 {
+    let correction = if yates { 0.5 } else { 0.0 };
+    let total = a.0 + a.1 + b.0 + b.1;
+    let (col_pos, col_neg) = (a.0 + b.0, a.1 + b.1);
+    [a, b]
+        .iter()
+        .flat_map(|&(p, n)| {
+            let row = p + n;
+            [(p, row * col_pos / total), (n, row * col_neg / total)]
+        })
+        .filter(|&(_, expected)| expected > 0.0)
+        .map(|(observed, expected)| {
+            ((observed - expected).abs() - correction).max(0.0).powi(2) / expected
+        })
+        .sum()
+}
+
+fn z_test(a: (i32, i32), b: (i32, i32)) -> f64 {
+    let (n_a, n_b) = ((a.0 + a.1) as f64, (b.0 + b.1) as f64);
+    let pooled = (a.0 + b.0) as f64 / (n_a + n_b);
+    let se = (pooled * (1.0 - pooled) * (1.0 / n_a + 1.0 / n_b)).sqrt();
+    let z = (a.0 as f64 / n_a - b.0 as f64 / n_b) / se;
+    erfc(z.abs() / std::f64::consts::SQRT_2)
+}
+
+/// Hypergeometric terms below this fraction of the mode are dropped.
+const FISHER_TOL: f64 = 1e-12;
+
+/// Sums the hypergeometric probabilities no larger than the observed one,
+/// walking out from the mode with the ratio of consecutive terms.
+fn fisher(a: (i32, i32), b: (i32, i32)) -> f64 {
+    let row = (a.0 + a.1) as f64;
+    let col = (a.0 + b.0) as f64;
+    let total = row + (b.0 + b.1) as f64;
+    let lo = (row + col - total).max(0.0) as i64;
+    let hi = row.min(col) as i64;
+    let mode = (((row + 1.0) * (col + 1.0) / (total + 2.0)).floor() as i64).clamp(lo, hi);
+    // P(x + 1) / P(x).
+    let ratio = |x: f64| (row - x) * (col - x) / ((x + 1.0) * (total - row - col + x + 1.0));
+
+    let observed = a.0 as i64;
+    let mut terms = vec![(mode, 1.0)];
+    let mut w = 1.0;
+    for x in mode..hi {
+        w *= ratio(x as f64);
+        if w < FISHER_TOL {
+            break;
+        }
+        terms.push((x + 1, w));
+    }
+    let mut w = 1.0;
+    for x in (lo..mode).rev() {
+        w /= ratio(x as f64);
+        if w < FISHER_TOL {
+            break;
+        }
+        terms.push((x, w));
+    }
+
+    let w_observed = terms.iter().find(|t| t.0 == observed).map_or(0.0, |t| t.1);
+    let sum: f64 = terms.iter().map(|t| t.1).sum();
+    let tail = terms
+        .iter()
+        .filter(|t| t.1 <= w_observed * (1.0 + 1e-7))
+        .fold(0.0, |acc, t| acc + t.1);
+    (tail / sum).min(1.0)
+}
+
+/// Complementary error function, with fractional error below 1.2e-7.
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.26551223
+        + t * (1.00002368
+            + t * (0.37409196
+                + t * (0.09678418
+                    + t * (-0.18628806
+                        + t * (0.27886807
+                            + t * (-1.13520398
+                                + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277))))))));
+    let r = t * poly.exp();
+    if x >= 0.0 { r } else { 2.0 - r }
+}
+
+/// Merges the adjacent pair of bins with the largest p-value until every pair
+/// passes `significance`. Bins are given by `splits`, the last prebin of each
+/// but the last of `n` prebins, and `counts(start, end)` gives the counts of
+/// prebins `start..=end`. Splits in `keep` are never merged away, nor pairs
+/// whose merged prebins `fits(start, end)` rejects, so some pairs may be
+/// left failing.
+pub(crate) fn merge_insignificant(
+    significance: &Significance,
+    mut splits: Vec<usize>,
+    n: usize,
+    keep: &[usize],
+    counts: impl Fn(usize, usize) -> (i32, i32),
+    fits: impl Fn(usize, usize) -> bool,
+) -> Vec<usize> {
+    loop {
+        let starts: Vec<usize> = [0]
+            .into_iter()
+            .chain(splits.iter().map(|&s| s + 1))
+            .collect();
+        let ends: Vec<usize> = splits.iter().copied().chain([n - 1]).collect();
+        let worst = (0..splits.len())
+            .filter(|&i| !keep.contains(&splits[i]) && fits(starts[i], ends[i + 1]))
+            .map(|i| {
+                let p = pvalue(
+                    significance.test,
+                    counts(starts[i], ends[i]),
+                    counts(starts[i + 1], ends[i + 1]),
+                );
+                (i, p)
+            })
+            .filter(|&(_, p)| p > significance.max_pvalue)
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        match worst {
+            Some((i, _)) => {
+                splits.remove(i);
+            }
+            None => return splits,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNIFICANCE: Significance = Significance {
+        max_pvalue: 0.05,
+        test: PValueTest::Chi2,
+    };
+
+    #[test]
+    fn merges_pairs_with_equal_rates() {
+        let splits = merge_insignificant(
+            &SIGNIFICANCE,
+            vec![0, 1, 2],
+            4,
+            &[],
+            |_, _| (10, 10),
+            |_, _| true,
+        );
+        assert!(splits.is_empty());
+    }
+
+    #[test]
+    fn keeps_pairs_whose_merge_does_not_fit() {
+        // No bin may span more than two prebins.
+        let splits = merge_insignificant(
+            &SIGNIFICANCE,
+            vec![0, 1, 2, 3, 4],
+            6,
+            &[],
+            |start, end| (10 * (end - start + 1) as i32, 10 * (end - start + 1) as i32),
+            |start, end| end - start < 2,
+        );
+        let ends: Vec<usize> = splits.iter().copied().chain([5]).collect();
+        let mut start = 0;
+        for end in ends {
+            assert!(end - start < 2);
+            start = end + 1;
+        }
+        assert!(splits.len() < 5);
+    }
+
+    #[test]
+    fn pvalues_match_reference_values() {
+        let (a, b) = ((10, 40), (20, 30));
+        assert!((pvalue(PValueTest::Chi2, a, b) - 0.04953).abs() < 1e-5);
+        assert!((pvalue(PValueTest::Z, a, b) - 0.02910).abs() < 1e-5);
+        assert!((pvalue(PValueTest::Fisher, a, b) - 0.04858).abs() < 1e-5);
+        assert_eq!(pvalue(PValueTest::Chi2, (0, 5), (0, 7)), 1.0);
+    }
+
+    #[test]
+    fn yates_correction_shrinks_the_statistic() {
+        let (a, b) = ((10.0, 40.0), (20.0, 30.0));
+        // Every cell is 5 away from its expected count of 15 or 35.
+        let expected = 25.0 * (2.0 / 15.0 + 2.0 / 35.0);
+        assert!((chi2_statistic(a, b, false) - expected).abs() < 1e-12);
+        let corrected = 20.25 * (2.0 / 15.0 + 2.0 / 35.0);
+        assert!((chi2_statistic(a, b, true) - corrected).abs() < 1e-12);
+    }
+}
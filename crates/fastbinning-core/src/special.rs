@@ -0,0 +1,119 @@
+use crate::error::BinningError;
+use crate::weights::BinWeights;
+use serde::{Deserialize, Serialize};
+
+/// A named group of sentinel values that share a standalone bin.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpecialGroup {
+    pub name: String,
+    pub values: Vec<f64>,
+}
+
+impl SpecialGroup {
+    pub fn new(name: impl Into<String>, values: Vec<f64>) -> Self {
+        Self {
+            name: name.into(),
+            values,
+        }
+    }
+
+    /// One group per value, named after the value itself.
+    pub fn from_values(values: &[f64]) -> Vec<Self> {
+        values
+            .iter()
+            .map(|&v| Self::new(v.to_string(), vec![v]))
+            .collect()
+    }
+}
+
+pub(crate) fn validate_groups(groups: &[SpecialGroup]) -> Result<(), BinningError> {
+    let mut seen: Vec<f64> = Vec::new();
+    for (i, group) in groups.iter().enumerate() {
+        if group.values.is_empty() {
+            return Err(BinningError::InvalidConfig(format!(
+                "Special group '{}' has no values",
+                group.name
+            )));
+        }
+        for &v in &group.values {
+            if !v.is_finite() {
+                return Err(BinningError::InvalidConfig(format!(
+                    "Special codes must be finite, found {}",
+                    v
+                )));
+            }
+            if seen.contains(&v) {
+                return Err(BinningError::InvalidConfig(format!(
+                    "Special code {} appears more than once",
+                    v
+                )));
+            }
+            seen.push(v);
+        }
+        if groups[..i].iter().any(|g| g.name == group.name) {
+            return Err(BinningError::InvalidConfig(format!(
+                "Duplicate special group name '{}'",
+                group.name
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Maps a value to the index of the special group containing it.
+pub(crate) struct SpecialLookup {
+    values: Vec<(f64, usize)>,
+}
+
+impl SpecialLookup {
+    pub fn new<'a>(groups: impl IntoIterator<Item = &'a SpecialGroup>) -> Self {
+        let mut values: Vec<(f64, usize)> = groups
+            .into_iter()
+            .enumerate()
+            .flat_map(|(g, group)| group.values.iter().map(move |&v| (v + 0.0, g)))
+            .collect();
+        values.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { values }
+    }
+
+    #[inline]
+    pub fn find(&self, v: f64) -> Option<usize> {
+        // `+ 0.0` folds -0.0 into 0.0 so both hit the same code.
+        let v = v + 0.0;
+        self.values
+            .binary_search_by(|probe| probe.0.total_cmp(&v))
+            .ok()
+            .map(|i| self.values[i].1)
+    }
+}
+
+/// Per-group tallies of the rows routed away from the regular bins.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpecialTally {
+    pub pos: i32,
+    pub neg: i32,
+    pub weights: BinWeights,
+}
+
+impl Default for SpecialTally {
+    fn default() -> Self {
+        Self {
+            pos: 0,
+            neg: 0,
+            weights: BinWeights { pos: 0.0, neg: 0.0 },
+        }
+    }
+}
+
+impl SpecialTally {
+    #[inline]
+    pub fn push(&mut self, target: i32, weight: f64) {
+        if target == 1 {
+            self.pos += 1;
+            self.weights.pos += weight;
+        } else {
+            self.neg += 1;
+            self.weights.neg += weight;
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::woeiv::{calc_event_rate, calc_woe_iv};
+use serde::{Deserialize, Serialize};
+
+/// Floor on the shares inside the PSI log term, so that empty bins stay finite.
+const PSI_FLOOR: f64 = 1e-4;
+
+/// One fitted bin compared between the development sample and a new one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StabilityBin {
+    pub bin_id: usize,
+    /// Share of the development sample, weighted as fitted.
+    pub expected_pct: f64,
+    /// Share of the new sample.
+    pub actual_pct: f64,
+    /// Contribution of the bin to the PSI.
+    pub psi: f64,
+    /// Set when the new sample comes with a target.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drift: Option<BinDrift>,
+}
+
+/// Outcome of a bin in the new sample, next to the fitted one.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BinDrift {
+    pub event_rate: f64,
+    /// WoE and IV are `None` when the new sample holds a single class, which
+    /// leaves them undefined.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub woe: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iv: Option<f64>,
+    /// `woe` minus the fitted WoE.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub woe_drift: Option<f64>,
+    /// `iv` minus the fitted IV.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iv_drift: Option<f64>,
+}
+
+/// Population stability of a new sample against the fitted bins: the PSI
+/// when the binned variable is a score, the CSI when it is a characteristic.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Stability {
+    pub bins: Vec<StabilityBin>,
+    pub psi: f64,
+    /// New rows that fall in no fitted bin: unseen categories, or missing
+    /// values when the fit saw none. They are left out of the shares.
+    pub n_unbinned: usize,
+}
+
+/// What the comparison needs of one fitted bin.
+pub(crate) struct Fitted {
+    pub bin_id: usize,
+    pub woe: f64,
+    pub iv: f64,
+    pub weight: f64,
+}
+
+/// Compares the new rows, given as the position in `fitted` of the bin each
+/// falls in and its target if there is one, against the fitted bins.
+pub(crate) fn compare(
+    fitted: &[Fitted],
+    rows: impl Iterator<Item = (Option<usize>, Option<i32>)>,
+    has_target: bool,
+) -> Stability {
+    let mut counts = vec![0usize; fitted.len()];
+    let mut events = vec![(0.0, 0.0); fitted.len()];
+    let mut n_unbinned = 0;
+    for (position, target) in rows {
+        let Some(b) = position else {
+            n_unbinned += 1;
+            continue;
+        };
+        counts[b] += 1;
+        match target {
+            Some(1) => events[b].0 += 1.0,
+            Some(_) => events[b].1 += 1.0,
+            None => {}
+        }
+    }
+
+    let n_binned: usize = counts.iter().sum();
+    let expected_total: f64 = fitted.iter().map(|f| f.weight).sum();
+    let total_pos: f64 = events.iter().map(|e| e.0).sum();
+    let total_neg: f64 = events.iter().map(|e| e.1).sum();
+
+    let bins: Vec<StabilityBin> = fitted
+        .iter()
+        .zip(counts.iter().zip(events.iter()))
+        .map(|(f, (&count, &(pos, neg)))| {
+            let expected_pct = if expected_total > 0.0 {
+                f.weight / expected_total
+            } else {
+                0.0
+            };
+            let actual_pct = if n_binned > 0 {
+                count as f64 / n_binned as f64
+            } else {
+                0.0
+            };
+            let psi = (actual_pct - expected_pct)
+                * (actual_pct.max(PSI_FLOOR) / expected_pct.max(PSI_FLOOR)).ln();
+            let drift = has_target.then(|| {
+                let woe_iv = (total_pos > 0.0 && total_neg > 0.0)
+                    .then(|| calc_woe_iv(pos, neg, total_pos, total_neg));
+                BinDrift {
+                    event_rate: calc_event_rate(pos, neg),
+                    woe: woe_iv.map(|(woe, _)| woe),
+                    iv: woe_iv.map(|(_, iv)| iv),
+                    woe_drift: woe_iv.map(|(woe, _)| woe - f.woe),
+                    iv_drift: woe_iv.map(|(_, iv)| iv - f.iv),
+                }
+            });
+            StabilityBin {
+                bin_id: f.bin_id,
+                expected_pct,
+                actual_pct,
+                psi,
+                drift,
+            }
+        })
+        .collect();
+    Stability {
+        psi: bins.iter().map(|b| b.psi).sum(),
+        bins,
+        n_unbinned,
+    }
+}
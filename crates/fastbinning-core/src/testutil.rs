@@ -0,0 +1,53 @@
+//! Synthetic samples shared by the unit tests.
+
+use ndarray::Array1;
+
+/// Deterministic uniform draws in `[0, 1)` from a 64-bit LCG.
+pub(crate) struct Lcg(u64);
+
+impl Lcg {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub(crate) fn uniform(&mut self) -> f64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Scores in `[0, 100)` on a 0.1 grid whose event rate drops from 30% to 10%.
+pub(crate) fn scores(n: usize, seed: u64) -> (Array1<f64>, Array1<i32>) {
+    let mut rng = Lcg::new(seed);
+    let x: Vec<f64> = (0..n)
+        .map(|_| (rng.uniform() * 1000.0).floor() / 10.0)
+        .collect();
+    let y: Vec<i32> = x
+        .iter()
+        .map(|&v| (rng.uniform() < 0.3 - 0.002 * v) as i32)
+        .collect();
+    (Array1::from(x), Array1::from(y))
+}
+
+/// Category codes 0..12 whose event rate grows with the code, with every
+/// 40th row missing as -1.
+pub(crate) fn categories(n: usize, seed: u64) -> (Vec<i32>, Vec<i32>) {
+    let mut rng = Lcg::new(seed);
+    let x: Vec<i32> = (0..n)
+        .map(|i| {
+            if i % 40 == 0 {
+                -1
+            } else {
+                (rng.uniform() * 12.0) as i32
+            }
+        })
+        .collect();
+    let y: Vec<i32> = x
+        .iter()
+        .map(|&c| (rng.uniform() < 0.05 + 0.02 * c.max(0) as f64) as i32)
+        .collect();
+    (x, y)
+}
@@ -0,0 +1,145 @@
+use crate::error::BinningError;
+
+pub(crate) fn check_length(
+    name: &'static str,
+    expected: usize,
+    found: usize,
+) -> Result<(), BinningError> {
+    if expected != found {
+        return Err(BinningError::LengthMismatch {
+            name,
+            expected,
+            found,
+        });
+    }
+    Ok(())
+}
+
+/// Checks sample weights against the rows they weight, given as
+/// `(is_missing, target)`: weights must be finite and non-negative, and
+/// both classes must keep a positive total weight among non-missing rows.
+pub(crate) fn check_weights<'a>(
+    w: impl ExactSizeIterator<Item = &'a f64>,
+    rows: impl ExactSizeIterator<Item = (bool, i32)>,
+) -> Result<(), BinningError> {
+    check_length("sample_weight", rows.len(), w.len())?;
+    let (mut w_pos, mut w_neg) = (0.0, 0.0);
+    for (&v, (is_missing, t)) in w.zip(rows) {
+        if !v.is_finite() || v < 0.0 {
+            return Err(BinningError::InvalidWeight(format!(
+                "weights must be finite and non-negative, found {}",
+                v
+            )));
+        }
+        if !is_missing {
+            if t == 1 {
+                w_pos += v;
+            } else {
+                w_neg += v;
+            }
+        }
+    }
+    if w_pos <= 0.0 || w_neg <= 0.0 {
+        return Err(BinningError::InvalidWeight(format!(
+            "{} among non-missing values have zero total weight",
+            if w_pos <= 0.0 { "events" } else { "non-events" }
+        )));
+    }
+    Ok(())
+}
+
+/// Checks a binary target against its feature, given as `(is_missing, is_infinite)`
+/// flags per row: `x` must have a finite non-missing value and `y` must hold
+/// both classes among non-missing rows.
+pub(crate) fn check_binary<'a>(
+    x: impl ExactSizeIterator<Item = (bool, bool)>,
+    y: impl ExactSizeIterator<Item = &'a i32>,
+) -> Result<(), BinningError> {
+    check_length("y", x.len(), y.len())?;
+    if x.len() == 0 {
+        return Err(BinningError::EmptyInput);
+    }
+
+    let (mut n_valid, mut n_pos, mut n_neg) = (0usize, 0usize, 0usize);
+    for ((is_missing, is_infinite), &t) in x.zip(y) {
+        if is_infinite {
+            return Err(BinningError::InfiniteValue);
+        }
+        match t {
+            0 | 1 => {}
+            other => return Err(BinningError::NonBinaryTarget(other)),
+        }
+        if !is_missing {
+            n_valid += 1;
+            if t == 1 {
+                n_pos += 1;
+            } else {
+                n_neg += 1;
+            }
+        }
+    }
+
+    if n_valid == 0 {
+        return Err(BinningError::AllMissing);
+    }
+    if n_pos == 0 {
+        return Err(BinningError::NoEvents);
+    }
+    if n_neg == 0 {
+        return Err(BinningError::NoNonEvents);
+    }
+    Ok(())
+}
+
+/// Checks a numerical feature on its own: non-empty, no infinities and at
+/// least one non-missing value.
+pub(crate) fn check_numerical<'a>(
+    x: impl ExactSizeIterator<Item = &'a f64>,
+) -> Result<(), BinningError> {
+    if x.len() == 0 {
+        return Err(BinningError::EmptyInput);
+    }
+    let mut n_valid = 0usize;
+    for &v in x {
+        if v.is_infinite() {
+            return Err(BinningError::InfiniteValue);
+        }
+        if !v.is_nan() {
+            n_valid += 1;
+        }
+    }
+    if n_valid == 0 {
+        return Err(BinningError::AllMissing);
+    }
+    Ok(())
+}
+
+/// Checks a sample scored against fitted bins, given as an `is_infinite` flag
+/// per row: it must be non-empty and finite. Unlike in fitting, every row may
+/// be missing, since a shift towards missing is what monitoring looks for.
+pub(crate) fn check_new_sample(x: impl ExactSizeIterator<Item = bool>) -> Result<(), BinningError> {
+    if x.len() == 0 {
+        return Err(BinningError::EmptyInput);
+    }
+    for is_infinite in x {
+        if is_infinite {
+            return Err(BinningError::InfiniteValue);
+        }
+    }
+    Ok(())
+}
+
+/// Checks the target of a sample scored against fitted bins: it must match
+/// the `n` rows and be binary, but may hold a single class.
+pub(crate) fn check_new_target<'a>(
+    n: usize,
+    y: impl ExactSizeIterator<Item = &'a i32>,
+) -> Result<(), BinningError> {
+    check_length("y", n, y.len())?;
+    for &t in y {
+        if t != 0 && t != 1 {
+            return Err(BinningError::NonBinaryTarget(t));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BinWeights {
+    pub pos: f64,
+    pub neg: f64,
+}
+
+impl BinWeights {
+    #[inline]
+    pub fn total(&self) -> f64 {
+        self.pos + self.neg
+    }
+}
@@ -0,0 +1,110 @@
+use crate::error::BinningError;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Per-bin separation measure summed by the DP. Gini and KS are not sums
+/// over bins, as they depend on the order of the bins, so they are not
+/// offered.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Divergence {
+    /// Information value (Jeffreys divergence).
+    #[default]
+    Iv,
+    /// Jensen-Shannon divergence.
+    Js,
+    /// Squared Hellinger distance.
+    Hellinger,
+    /// Triangular discrimination.
+    Triangular,
+}
+
+impl FromStr for Divergence {
+    type Err = BinningError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "iv" => Ok(Divergence::Iv),
+            "js" => Ok(Divergence::Js),
+            "hellinger" => Ok(Divergence::Hellinger),
+            "triangular" => Ok(Divergence::Triangular),
+            "gini" | "ks" => Err(BinningError::InvalidConfig(format!(
+                "Divergence '{}' is not supported: it is not a sum over bins, which the \
+                 solver maximizes; expected one of 'iv', 'js', 'hellinger', 'triangular'",
+                s
+            ))),
+            other => Err(BinningError::InvalidConfig(format!(
+                "Unknown divergence '{}', expected one of 'iv', 'js', 'hellinger', \
+                 'triangular'",
+                other
+            ))),
+        }
+    }
+}
+
+#[inline]
+pub fn calc_woe_iv(pos: f64, neg: f64, total_pos: f64, total_neg: f64) -> (f64, f64) {
+    let py = if pos == 0.0 {
+        0.5 / total_pos
+    } else {
+        pos / total_pos
+    };
+    let pn = if neg == 0.0 {
+        0.5 / total_neg
+    } else {
+        neg / total_neg
+    };
+
+    let woe = (py / pn).ln();
+    let iv = (py - pn) * woe;
+
+    (woe, iv)
+}
+
+#[inline]
+pub fn calc_event_rate(pos: f64, neg: f64) -> f64 {
+    if pos + neg > 0.0 {
+        pos / (pos + neg)
+    } else {
+        0.0
+    }
+}
+
+/// Contribution of one bin to `divergence`. Unlike IV, the other measures are
+/// bounded, so empty classes need no smoothing.
+#[inline]
+pub fn calc_divergence(
+    divergence: Divergence,
+    pos: f64,
+    neg: f64,
+    total_pos: f64,
+    total_neg: f64,
+) -> f64 {
+    let py = pos / total_pos;
+    let pn = neg / total_neg;
+    match divergence {
+        Divergence::Iv => calc_woe_iv(pos, neg, total_pos, total_neg).1,
+        Divergence::Js => {
+            let m = (py + pn) / 2.0;
+            let kl = |p: f64| if p > 0.0 { p * (p / m).ln() } else { 0.0 };
+            (kl(py) + kl(pn)) / 2.0
+        }
+        Divergence::Hellinger => (py.sqrt() - pn.sqrt()).powi(2) / 2.0,
+        Divergence::Triangular if py + pn > 0.0 => (py - pn).powi(2) / (py + pn),
+        Divergence::Triangular => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn order_dependent_measures_are_rejected() {
+        assert_eq!("js".parse::<Divergence>().unwrap(), Divergence::Js);
+        assert!(matches!(
+            "gini".parse::<Divergence>(),
+            Err(BinningError::InvalidConfig(_))
+        ));
+    }
+}
@@ -0,0 +1,67 @@
+//! The core crate on its own, as a Rust service would use it.
+
+use fastbinning_core::{CategoricalBinning, CategoricalConfig, NumericalBinning, NumericalConfig};
+use ndarray::Array1;
+
+/// `n` rows of `x = i % 50` whose event rate falls with `x`, with every
+/// 25th row missing.
+fn numerical_sample(n: usize) -> (Array1<f64>, Array1<i32>) {
+    let x: Vec<f64> = (0..n)
+        .map(|i| {
+            if i % 25 == 0 {
+                f64::NAN
+            } else {
+                (i % 50) as f64
+            }
+        })
+        .collect();
+    let y: Vec<i32> = (0..n)
+        .map(|i| ((i * 7) % 100 < 40 - (i % 50) / 2) as i32)
+        .collect();
+    (Array1::from(x), Array1::from(y))
+}
+
+#[test]
+fn numerical_fit_and_transform() {
+    let (x, y) = numerical_sample(2000);
+    let config = NumericalConfig::new(5, 0.05, 0.5).unwrap();
+    let model = NumericalBinning::new(config)
+        .fit(x.view(), y.view())
+        .unwrap();
+
+    let missing = model.bins.iter().find(|b| b.is_missing).unwrap();
+    let regular: Vec<_> = model.bins.iter().filter(|b| !b.is_missing).collect();
+    assert!(regular.len() > 1);
+    assert_eq!(regular[0].range.0, f64::NEG_INFINITY);
+    assert_eq!(regular[regular.len() - 1].range.1, f64::INFINITY);
+    let n_rows: i32 = model.bins.iter().map(|b| b.pos + b.neg).sum();
+    assert_eq!(n_rows as usize, x.len());
+
+    let woe = model.transform(x.view());
+    assert_eq!(woe.len(), x.len());
+    assert_eq!(woe[0], missing.woe);
+    let first = regular.iter().find(|b| x[1] <= b.range.1).unwrap();
+    assert_eq!(woe[1], first.woe);
+}
+
+#[test]
+fn categorical_fit_and_transform() {
+    let x: Vec<i32> = (0..2000)
+        .map(|i| if i % 40 == 0 { -1 } else { i % 8 })
+        .collect();
+    let y: Vec<i32> = (0..2000)
+        .map(|i| ((i * 7) % 100 < 5 + 5 * (i % 8)) as i32)
+        .collect();
+    let config = CategoricalConfig::new(4, 0.05, 0.6).unwrap();
+    let model = CategoricalBinning::new(config).fit(&x, &y).unwrap();
+
+    let woe = model.transform(&x).unwrap();
+    for (&category, &w) in x.iter().zip(&woe) {
+        let bin = model
+            .bins
+            .iter()
+            .find(|b| b.indices.contains(&category))
+            .unwrap();
+        assert_eq!(w, bin.woe);
+    }
+}
@@ -0,0 +1,54 @@
+import time
+
+import numpy as np
+from fastbinning import BinningProcess, CategoricalBinning, NumericalBinning
+
+if __name__ == "__main__":
+    # -------------------------------------------------------------------------
+    # Data Generation: 1 Million Samples, 50 numerical + 10 categorical columns
+    # -------------------------------------------------------------------------
+    n_samples = 1_000_000
+    n_numerical, n_categorical = 50, 10
+    np.random.seed(42)
+
+    x_num = np.random.normal(0, 1, size=(n_samples, n_numerical))
+    x_cat = np.random.randint(0, 8, size=(n_samples, n_categorical)).astype(np.float64)
+    logit = x_num[:, :5].sum(axis=1) + 0.3 * x_cat[:, 0]
+    y = (np.random.rand(n_samples) < 1 / (1 + np.exp(-logit))).astype(np.int32)
+
+    # Categorical columns share the float matrix; NaN marks a missing category
+    x_cat[np.random.rand(n_samples, n_categorical) < 0.01] = np.nan
+    x = np.hstack([x_num, x_cat])
+
+    # -------------------------------------------------------------------------
+    # Configure one binning per column
+    # -------------------------------------------------------------------------
+    binnings = [
+        NumericalBinning(max_bins=10, min_bin_pct=0.05, max_bin_pct=0.3)
+        for _ in range(n_numerical)
+    ] + [
+        CategoricalBinning(max_bins=5, min_bin_pct=0.05, max_bin_pct=0.5)
+        for _ in range(n_categorical)
+    ]
+    process = BinningProcess(binnings)
+
+    # -------------------------------------------------------------------------
+    # Fit all columns in parallel
+    # -------------------------------------------------------------------------
+    start_time = time.perf_counter()
+    summary = process.fit(x, y)
+    end_time = time.perf_counter()
+    print(f"Execution Fitting Time: {(end_time - start_time) * 1000:.2f} ms")
+
+    print("-" * 40)
+    print(f"{'Index':<6} | {'Kind':<12} | {'Bins':<5} | {'IV':<8}")
+    print("-" * 40)
+    for s in sorted(summary, key=lambda s: s.iv, reverse=True)[:10]:
+        print(f"{s.index:<6} | {s.kind:<12} | {s.n_bins:<5} | {s.iv:<8.4f}")
+    print("-" * 40)
+
+    # -------------------------------------------------------------------------
+    # Transform to a WoE matrix
+    # -------------------------------------------------------------------------
+    woe = process.transform(x)
+    print(f"WoE matrix shape: {woe.shape}")
@@ -15,9 +15,12 @@ def print_bins(bins: List[PyNumBin]):
 
     total_iv = 0
     for b in bins:
-        range_str = (
-            f"({b.range[0]:>4.2f}, {b.range[1]:>4.2f}]" if not b.is_missing else "NaN"
-        )
+        if b.is_special:
+            range_str = f"special: {b.special_name}"
+        elif b.is_missing:
+            range_str = "NaN"
+        else:
+            range_str = f"({b.range[0]:>4.2f}, {b.range[1]:>4.2f}]"
         print(
             f"{b.bin_id:<3} | {range_str:<15} | {b.count:<10} | {b.bin_pct:<8.4f} | "
             f"{b.pos:<8} | {b.neg:<8} | {b.woe:<8.4f} | {b.iv:<8.4f} | {b.event_rate:<10.4f} | {b.is_missing}"
@@ -44,13 +47,19 @@ if __name__ == "__main__":
     nan_indices = np.random.choice(pos_indices, 5000, replace=False)
     x_num[nan_indices] = np.nan
 
+    # Inject a bureau-style sentinel code (-999) that must stay out of the
+    # regular bins
+    special_indices = np.random.choice(n_samples, 20000, replace=False)
+    x_num[special_indices] = -999.0
+
     # -------------------------------------------------------------------------
     # Configure Numerical Binning
     # -------------------------------------------------------------------------
     # max_bins: Final number of bins to produce
     # min_bin_pct: Minimum sample size required for each bin (10%)
+    # special_codes: Sentinel values binned on their own
     numerical_binning = NumericalBinning(
-        max_bins=10, min_bin_pct=0.05, max_bin_pct=0.15
+        max_bins=10, min_bin_pct=0.05, max_bin_pct=0.15, special_codes=[-999.0]
     )
 
     # -------------------------------------------------------------------------
@@ -1,3 +1,33 @@
-from .fastbinning import CategoricalBinning, NumericalBinning, PyCatBin, PyNumBin
+from .fastbinning import (
+    BinningProcess,
+    CategoricalBinning,
+    ContinuousNumericalBinning,
+    MulticlassNumericalBinning,
+    NumericalBinning,
+    PyCatBin,
+    PyCatEvaluation,
+    PyContBin,
+    PyMultiBin,
+    PyNumBin,
+    PyNumEvaluation,
+    PyStability,
+    PyStabilityBin,
+    PyVariableSummary,
+)
 
-__all__ = ["NumericalBinning", "CategoricalBinning", "PyNumBin", "PyCatBin"]
+__all__ = [
+    "NumericalBinning",
+    "CategoricalBinning",
+    "ContinuousNumericalBinning",
+    "MulticlassNumericalBinning",
+    "BinningProcess",
+    "PyNumBin",
+    "PyCatBin",
+    "PyContBin",
+    "PyMultiBin",
+    "PyNumEvaluation",
+    "PyCatEvaluation",
+    "PyStability",
+    "PyStabilityBin",
+    "PyVariableSummary",
+]
@@ -1,4 +1,4 @@
-from typing import List, Tuple
+from typing import Dict, List, Optional, Sequence, Tuple, Union
 
 import numpy as np
 from numpy.typing import NDArray
@@ -14,6 +14,25 @@ class PyNumBin:
     iv: float
     event_rate: float
     is_missing: bool
+    is_special: bool
+    special_name: Optional[str]
+    pvalue: Optional[float]
+    def __init__(
+        self,
+        bin_id: int,
+        range: Tuple[float, float],
+        count: int,
+        bin_pct: float,
+        pos: int,
+        neg: int,
+        woe: float,
+        iv: float,
+        event_rate: float,
+        is_missing: bool,
+        is_special: bool = False,
+        special_name: Optional[str] = None,
+        pvalue: Optional[float] = None,
+    ): ...
 
 class PyCatBin:
     bin_id: int
@@ -26,23 +45,290 @@ class PyCatBin:
     iv: float
     event_rate: float
     is_missing: bool
+    is_special: bool
+    pvalue: Optional[float]
+    def __init__(
+        self,
+        bin_id: int,
+        indices: List[int],
+        count: int,
+        bin_pct: float,
+        pos: int,
+        neg: int,
+        woe: float,
+        iv: float,
+        event_rate: float,
+        is_missing: bool,
+        is_special: bool = False,
+        pvalue: Optional[float] = None,
+    ): ...
+
+class PyContBin:
+    bin_id: int
+    range: Tuple[float, float]
+    count: int
+    bin_pct: float
+    sum: float
+    mean: float
+    std: float
+    min: float
+    max: float
+    is_missing: bool
+    def __init__(
+        self,
+        bin_id: int,
+        range: Tuple[float, float],
+        count: int,
+        bin_pct: float,
+        sum: float,
+        mean: float,
+        std: float,
+        min: float,
+        max: float,
+        is_missing: bool,
+    ): ...
+
+class PyMultiBin:
+    bin_id: int
+    range: Tuple[float, float]
+    count: int
+    bin_pct: float
+    counts: List[int]
+    event_rates: List[float]
+    woe: List[float]
+    iv: float
+    is_missing: bool
+    def __init__(
+        self,
+        bin_id: int,
+        range: Tuple[float, float],
+        count: int,
+        bin_pct: float,
+        counts: List[int],
+        event_rates: List[float],
+        woe: List[float],
+        iv: float,
+        is_missing: bool,
+    ): ...
 
 class NumericalBinning:
-    def __init__(self, max_bins: int, min_bin_pct: float, max_bin_pct: float): ...
-    def fit(self, x: NDArray[np.float64], y: NDArray[np.int32]) -> List[PyNumBin]: ...
+    def __init__(
+        self,
+        max_bins: int,
+        min_bin_pct: float,
+        max_bin_pct: float,
+        special_codes: Optional[Union[List[float], Dict[str, List[float]]]] = None,
+        monotonic_trend: str = "auto_asc_desc",
+        penalty: str = "log_barrier",
+        penalty_strength: float = 5.0,
+        target_bin_pct: Optional[float] = None,
+        solver: str = "fast",
+        divergence: str = "iv",
+        prebinning: str = "quantile",
+        n_prebins: Optional[int] = None,
+        user_splits: Optional[Sequence[float]] = None,
+        locked_splits: Optional[Sequence[float]] = None,
+        min_bin_n_event: Optional[int] = None,
+        min_bin_n_nonevent: Optional[int] = None,
+        max_bin_n_event: Optional[int] = None,
+        max_pvalue: Optional[float] = None,
+        pvalue_test: str = "chi2",
+        min_event_rate_diff: Optional[float] = None,
+        min_woe_diff: Optional[float] = None,
+    ): ...
+    def fit(
+        self,
+        x: NDArray[np.float64],
+        y: NDArray[np.int32],
+        sample_weight: Optional[NDArray[np.float64]] = None,
+    ) -> List[PyNumBin]: ...
     def transform(self, x: NDArray[np.float64]) -> NDArray[np.float64]: ...
     def fit_transform(
-        self, x: NDArray[np.float64], y: NDArray[np.int32]
+        self,
+        x: NDArray[np.float64],
+        y: NDArray[np.int32],
+        sample_weight: Optional[NDArray[np.float64]] = None,
     ) -> NDArray[np.float64]: ...
+    def stability(
+        self,
+        x_new: NDArray[np.float64],
+        y_new: Optional[NDArray[np.int32]] = None,
+    ) -> PyStability: ...
+    def evaluate(
+        self, x: NDArray[np.float64], y: NDArray[np.int32]
+    ) -> PyNumEvaluation: ...
+    def to_json(self) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> "NumericalBinning": ...
+    def merge_bins(self, bin_id: int) -> List[str]: ...
+    def split_bin(self, bin_id: int, value: float) -> List[str]: ...
+    @property
+    def violations(self) -> List[str]: ...
+    @property
+    def raw_iv(self) -> Optional[float]: ...
+    @property
+    def penalized_score(self) -> Optional[float]: ...
+    @property
+    def optimality_gap(self) -> Optional[float]: ...
+    @property
+    def trend(self) -> Optional[str]: ...
     @property
     def bins(self) -> List[PyNumBin]: ...
 
 class CategoricalBinning:
-    def __init__(self, max_bins: int, min_bin_pct: float, max_bin_pct: float): ...
-    def fit(self, x: NDArray[np.int32], y: NDArray[np.int32]) -> List[PyCatBin]: ...
+    def __init__(
+        self,
+        max_bins: int,
+        min_bin_pct: float,
+        max_bin_pct: float,
+        missing_code: int = -1,
+        special_codes: Optional[List[int]] = None,
+        unseen: Union[str, float, None] = None,
+        divergence: str = "iv",
+        min_bin_n_event: Optional[int] = None,
+        min_bin_n_nonevent: Optional[int] = None,
+        max_bin_n_event: Optional[int] = None,
+        max_pvalue: Optional[float] = None,
+        pvalue_test: str = "chi2",
+        min_event_rate_diff: Optional[float] = None,
+        min_woe_diff: Optional[float] = None,
+    ): ...
+    def fit(
+        self,
+        x: NDArray[np.int32],
+        y: NDArray[np.int32],
+        sample_weight: Optional[NDArray[np.float64]] = None,
+    ) -> List[PyCatBin]: ...
     def transform(self, x: NDArray[np.int32]) -> NDArray[np.float64]: ...
     def fit_transform(
-        self, x: NDArray[np.int32], y: NDArray[np.int32]
+        self,
+        x: NDArray[np.int32],
+        y: NDArray[np.int32],
+        sample_weight: Optional[NDArray[np.float64]] = None,
     ) -> NDArray[np.float64]: ...
+    def stability(
+        self,
+        x_new: NDArray[np.int32],
+        y_new: Optional[NDArray[np.int32]] = None,
+    ) -> PyStability: ...
+    def evaluate(
+        self, x: NDArray[np.int32], y: NDArray[np.int32]
+    ) -> PyCatEvaluation: ...
+    def to_json(self) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> "CategoricalBinning": ...
+    def merge_bins(self, bin_id: int) -> List[str]: ...
+    def move_category(self, category: int, to_bin: int) -> List[str]: ...
+    @property
+    def violations(self) -> List[str]: ...
     @property
     def bins(self) -> List[PyCatBin]: ...
+    @property
+    def n_unseen(self) -> int: ...
+
+class ContinuousNumericalBinning:
+    def __init__(
+        self,
+        max_bins: int,
+        min_bin_pct: float,
+        max_bin_pct: float,
+        monotonic_trend: str = "auto_asc_desc",
+        penalty: str = "log_barrier",
+        penalty_strength: float = 5.0,
+        target_bin_pct: Optional[float] = None,
+        prebinning: str = "quantile",
+        n_prebins: Optional[int] = None,
+    ): ...
+    def fit(self, x: NDArray[np.float64], y: NDArray[np.float64]) -> List[PyContBin]: ...
+    def transform(self, x: NDArray[np.float64]) -> NDArray[np.float64]: ...
+    def fit_transform(
+        self, x: NDArray[np.float64], y: NDArray[np.float64]
+    ) -> NDArray[np.float64]: ...
+    def to_json(self) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> "ContinuousNumericalBinning": ...
+    @property
+    def bins(self) -> List[PyContBin]: ...
+
+class MulticlassNumericalBinning:
+    def __init__(
+        self,
+        max_bins: int,
+        min_bin_pct: float,
+        max_bin_pct: float,
+        prebinning: str = "quantile",
+        n_prebins: Optional[int] = None,
+    ): ...
+    def fit(self, x: NDArray[np.float64], y: NDArray[np.int32]) -> List[PyMultiBin]: ...
+    def transform(self, x: NDArray[np.float64]) -> NDArray[np.float64]: ...
+    def fit_transform(
+        self, x: NDArray[np.float64], y: NDArray[np.int32]
+    ) -> NDArray[np.float64]: ...
+    def to_json(self) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> "MulticlassNumericalBinning": ...
+    @property
+    def classes(self) -> List[int]: ...
+    @property
+    def bins(self) -> List[PyMultiBin]: ...
+
+class PyStabilityBin:
+    bin_id: int
+    expected_pct: float
+    actual_pct: float
+    psi: float
+    event_rate: Optional[float]
+    woe: Optional[float]
+    iv: Optional[float]
+    woe_drift: Optional[float]
+    iv_drift: Optional[float]
+
+class PyStability:
+    psi: float
+    n_unbinned: int
+    bins: List[PyStabilityBin]
+
+class PyNumEvaluation:
+    bins: List[PyNumBin]
+    sign_flips: List[bool]
+    order_flips: List[bool]
+    order_holds: bool
+    iv: float
+    n_unbinned: int
+
+class PyCatEvaluation:
+    bins: List[PyCatBin]
+    sign_flips: List[bool]
+    order_flips: List[bool]
+    order_holds: bool
+    iv: float
+    n_unbinned: int
+
+class PyVariableSummary:
+    index: int
+    kind: str
+    n_bins: int
+    iv: float
+
+class BinningProcess:
+    def __init__(
+        self, binnings: Sequence[Union[NumericalBinning, CategoricalBinning]]
+    ): ...
+    def fit(
+        self,
+        x: NDArray[np.float64],
+        y: NDArray[np.int32],
+        sample_weight: Optional[NDArray[np.float64]] = None,
+    ) -> List[PyVariableSummary]: ...
+    def transform(self, x: NDArray[np.float64]) -> NDArray[np.float64]: ...
+    def fit_transform(
+        self,
+        x: NDArray[np.float64],
+        y: NDArray[np.int32],
+        sample_weight: Optional[NDArray[np.float64]] = None,
+    ) -> NDArray[np.float64]: ...
+    @property
+    def summary(self) -> List[PyVariableSummary]: ...
+    def to_json(self) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> "BinningProcess": ...
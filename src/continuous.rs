@@ -0,0 +1,268 @@
+use crate::{BinningArgs, BinningState, to_pyerr};
+use fastbinning_core::{
+    ContBin, ContinuousConfig, ContinuousModel, MonotonicTrend, Prebinning, Regularization,
+    continuous::ContinuousBinning as CoreContinuousBinning,
+};
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use pyo3::types::PyType;
+
+type PyContBinState = (usize, (f64, f64), i32, f64, f64, f64, f64, f64, f64, bool);
+
+#[pyclass(module = "fastbinning", skip_from_py_object)]
+#[derive(Clone)]
+pub struct PyContBin {
+    #[pyo3(get)]
+    pub bin_id: usize,
+    #[pyo3(get)]
+    pub range: (f64, f64),
+    #[pyo3(get)]
+    pub count: i32,
+    #[pyo3(get)]
+    pub bin_pct: f64,
+    #[pyo3(get)]
+    pub sum: f64,
+    #[pyo3(get)]
+    pub mean: f64,
+    #[pyo3(get)]
+    pub std: f64,
+    #[pyo3(get)]
+    pub min: f64,
+    #[pyo3(get)]
+    pub max: f64,
+    #[pyo3(get)]
+    pub is_missing: bool,
+}
+
+#[pymethods]
+impl PyContBin {
+    #[new]
+    #[allow(clippy::too_many_arguments)]
+    pub fn pynew(
+        bin_id: usize,
+        range: (f64, f64),
+        count: i32,
+        bin_pct: f64,
+        sum: f64,
+        mean: f64,
+        std: f64,
+        min: f64,
+        max: f64,
+        is_missing: bool,
+    ) -> Self {
+        Self {
+            bin_id,
+            range,
+            count,
+            bin_pct,
+            sum,
+            mean,
+            std,
+            min,
+            max,
+            is_missing,
+        }
+    }
+
+    pub fn __getstate__(&self) -> PyContBinState {
+        (
+            self.bin_id,
+            self.range,
+            self.count,
+            self.bin_pct,
+            self.sum,
+            self.mean,
+            self.std,
+            self.min,
+            self.max,
+            self.is_missing,
+        )
+    }
+
+    pub fn __setstate__(&mut self, state: PyContBinState) {
+        (
+            self.bin_id,
+            self.range,
+            self.count,
+            self.bin_pct,
+            self.sum,
+            self.mean,
+            self.std,
+            self.min,
+            self.max,
+            self.is_missing,
+        ) = state;
+    }
+
+    pub fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, PyContBinState) {
+        (slf.get_type(), slf.borrow().__getstate__())
+    }
+}
+
+impl PyContBin {
+    fn from_bin(b: &ContBin, total_count: i32) -> Self {
+        let bin_pct = if total_count > 0 {
+            b.count as f64 / total_count as f64
+        } else {
+            0.0
+        };
+        PyContBin {
+            bin_id: b.bin_id,
+            range: b.range,
+            count: b.count,
+            bin_pct,
+            sum: b.sum,
+            mean: b.mean,
+            std: b.std,
+            min: b.min,
+            max: b.max,
+            is_missing: b.is_missing,
+        }
+    }
+}
+
+#[pyclass(module = "fastbinning")]
+pub struct ContinuousNumericalBinning {
+    pub inner: CoreContinuousBinning,
+    pub _bins: Option<Vec<ContBin>>,
+}
+
+#[pymethods]
+impl ContinuousNumericalBinning {
+    #[new]
+    #[pyo3(signature = (
+        max_bins, min_bin_pct, max_bin_pct, monotonic_trend="auto_asc_desc",
+        penalty="log_barrier", penalty_strength=5.0, target_bin_pct=None, prebinning="quantile",
+        n_prebins=None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn pynew(
+        max_bins: usize,
+        min_bin_pct: f64,
+        max_bin_pct: f64,
+        monotonic_trend: &str,
+        penalty: &str,
+        penalty_strength: f64,
+        target_bin_pct: Option<f64>,
+        prebinning: &str,
+        n_prebins: Option<usize>,
+    ) -> PyResult<Self> {
+        let monotonic_trend: MonotonicTrend = monotonic_trend.parse().map_err(to_pyerr)?;
+        let regularization = Regularization {
+            family: penalty.parse().map_err(to_pyerr)?,
+            strength: penalty_strength,
+            target_bin_pct,
+        };
+        let prebinning = Prebinning {
+            method: prebinning.parse().map_err(to_pyerr)?,
+            n_bins: n_prebins,
+        };
+        let config = ContinuousConfig::new(max_bins, min_bin_pct, max_bin_pct)
+            .and_then(|c| c.with_monotonic_trend(monotonic_trend))
+            .and_then(|c| c.with_regularization(regularization))
+            .and_then(|c| c.with_prebinning(prebinning))
+            .map_err(to_pyerr)?;
+        Ok(Self {
+            inner: CoreContinuousBinning::new(config),
+            _bins: None,
+        })
+    }
+
+    pub fn fit(
+        &mut self,
+        py: Python<'_>,
+        x: PyReadonlyArray1<f64>,
+        y: PyReadonlyArray1<f64>,
+    ) -> PyResult<Vec<PyContBin>> {
+        let x_owned = x.as_array().to_owned();
+        let y_owned = y.as_array().to_owned();
+        let inner = &self.inner;
+        let results = py.detach(|| inner.execute_fit(x_owned.view(), y_owned.view()));
+        self._bins = Some(results.map_err(to_pyerr)?);
+        self.bins()
+    }
+
+    pub fn transform<'py>(
+        &self,
+        py: Python<'py>,
+        x: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<Bound<'py, PyArray1<f64>>> {
+        let _bins = self._bins.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before transform()",
+        ))?;
+        let x_owned = x.as_array().to_owned();
+        let output: Vec<f64> =
+            py.detach(|| CoreContinuousBinning::execute_transform(x_owned.view(), _bins));
+        Ok(output.into_pyarray(py))
+    }
+
+    pub fn fit_transform<'py>(
+        &mut self,
+        py: Python<'py>,
+        x: PyReadonlyArray1<'py, f64>,
+        y: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<Bound<'py, PyArray1<f64>>> {
+        self.fit(py, x.clone(), y)?;
+        self.transform(py, x)
+    }
+
+    pub fn to_json(&self) -> PyResult<String> {
+        let bins = self._bins.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before to_json()",
+        ))?;
+        let model = ContinuousModel {
+            config: self.inner.config.clone(),
+            bins: bins.clone(),
+        };
+        model.to_json().map_err(to_pyerr)
+    }
+
+    #[staticmethod]
+    pub fn from_json(json: &str) -> PyResult<Self> {
+        let model = ContinuousModel::from_json(json).map_err(to_pyerr)?;
+        Ok(Self {
+            inner: CoreContinuousBinning::new(model.config),
+            _bins: Some(model.bins),
+        })
+    }
+
+    pub fn __getstate__(&self) -> PyResult<BinningState> {
+        let model = ContinuousModel {
+            config: self.inner.config.clone(),
+            bins: self._bins.clone().unwrap_or_default(),
+        };
+        let json = model.to_json().map_err(to_pyerr)?;
+        Ok((json, self._bins.is_some()))
+    }
+
+    pub fn __setstate__(&mut self, state: BinningState) -> PyResult<()> {
+        let (json, is_fitted) = state;
+        let model = ContinuousModel::from_json(&json).map_err(to_pyerr)?;
+        self.inner = CoreContinuousBinning::new(model.config);
+        self._bins = is_fitted.then_some(model.bins);
+        Ok(())
+    }
+
+    pub fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> PyResult<(Bound<'py, PyType>, BinningArgs, BinningState)> {
+        let this = slf.borrow();
+        let config = &this.inner.config;
+        let args = (config.max_bins, config.min_bin_pct, config.max_bin_pct);
+        Ok((slf.get_type(), args, this.__getstate__()?))
+    }
+
+    #[getter]
+    pub fn bins(&self) -> PyResult<Vec<PyContBin>> {
+        let bins = self._bins.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before 'bins'",
+        ))?;
+        let total_count: i32 = bins.iter().map(|b| b.count).sum();
+        let py_results = bins
+            .iter()
+            .map(|b| PyContBin::from_bin(b, total_count))
+            .collect();
+        Ok(py_results)
+    }
+}
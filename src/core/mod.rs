@@ -1,5 +0,0 @@
-pub mod categorical;
-pub mod numerical;
-pub mod precategorical;
-pub mod prenumerical;
-pub mod woeiv;
@@ -0,0 +1,73 @@
+use crate::{PyCatBin, PyNumBin};
+use fastbinning_core::{CatBin, Evaluation, NumBin};
+use pyo3::prelude::*;
+
+#[pyclass(module = "fastbinning", skip_from_py_object)]
+#[derive(Clone)]
+pub struct PyNumEvaluation {
+    #[pyo3(get)]
+    pub bins: Vec<PyNumBin>,
+    #[pyo3(get)]
+    pub sign_flips: Vec<bool>,
+    #[pyo3(get)]
+    pub order_flips: Vec<bool>,
+    #[pyo3(get)]
+    pub order_holds: bool,
+    #[pyo3(get)]
+    pub iv: f64,
+    #[pyo3(get)]
+    pub n_unbinned: usize,
+}
+
+impl PyNumEvaluation {
+    pub(crate) fn from_evaluation(e: &Evaluation<NumBin>) -> Self {
+        let total_weight: f64 = e.bins.iter().map(|b| b.total_weight()).sum();
+        PyNumEvaluation {
+            bins: e
+                .bins
+                .iter()
+                .map(|b| PyNumBin::from_bin(b, total_weight))
+                .collect(),
+            sign_flips: e.flips.iter().map(|f| f.sign).collect(),
+            order_flips: e.flips.iter().map(|f| f.order).collect(),
+            order_holds: e.order_holds(),
+            iv: e.iv,
+            n_unbinned: e.n_unbinned,
+        }
+    }
+}
+
+#[pyclass(module = "fastbinning", skip_from_py_object)]
+#[derive(Clone)]
+pub struct PyCatEvaluation {
+    #[pyo3(get)]
+    pub bins: Vec<PyCatBin>,
+    #[pyo3(get)]
+    pub sign_flips: Vec<bool>,
+    #[pyo3(get)]
+    pub order_flips: Vec<bool>,
+    #[pyo3(get)]
+    pub order_holds: bool,
+    #[pyo3(get)]
+    pub iv: f64,
+    #[pyo3(get)]
+    pub n_unbinned: usize,
+}
+
+impl PyCatEvaluation {
+    pub(crate) fn from_evaluation(e: &Evaluation<CatBin>) -> Self {
+        let total_weight: f64 = e.bins.iter().map(|b| b.total_weight()).sum();
+        PyCatEvaluation {
+            bins: e
+                .bins
+                .iter()
+                .map(|b| PyCatBin::from_bin(b, total_weight))
+                .collect(),
+            sign_flips: e.flips.iter().map(|f| f.sign).collect(),
+            order_flips: e.flips.iter().map(|f| f.order).collect(),
+            order_holds: e.order_holds(),
+            iv: e.iv,
+            n_unbinned: e.n_unbinned,
+        }
+    }
+}
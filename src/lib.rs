@@ -1,10 +1,90 @@
+use fastbinning_core::{
+    BinningError, CatBin, CategoricalConfig, CategoricalModel, Divergence, EventLimits,
+    MonotonicTrend, NumBin, NumericalConfig, NumericalModel, PValueTest, Prebinning,
+    Regularization, Separation, Significance, Solver, SpecialGroup, UnseenPolicy, UserSplits,
+    categorical::CategoricalBinning as CoreCategoricalBinning,
+    numerical::NumericalBinning as CoreNumericalBinning,
+};
 use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
 use pyo3::exceptions::{PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
-mod core;
-use crate::core::categorical::CatBin;
-use crate::core::numerical::NumBin;
-#[pyclass(skip_from_py_object)]
+use pyo3::types::{PyDict, PyType};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+mod continuous;
+mod evaluate;
+mod multiclass;
+mod process;
+mod stability;
+use crate::continuous::{ContinuousNumericalBinning, PyContBin};
+use crate::evaluate::{PyCatEvaluation, PyNumEvaluation};
+use crate::multiclass::{MulticlassNumericalBinning, PyMultiBin};
+use crate::process::{BinningProcess, PyVariableSummary};
+use crate::stability::{PyStability, PyStabilityBin};
+
+pub(crate) fn to_pyerr(err: BinningError) -> PyErr {
+    PyValueError::new_err(err.to_string())
+}
+
+type PyNumBinArgs = (usize, (f64, f64), i32, f64, i32, i32, f64, f64, f64, bool);
+type PyNumBinState = (
+    usize,
+    (f64, f64),
+    i32,
+    f64,
+    i32,
+    i32,
+    f64,
+    f64,
+    f64,
+    bool,
+    (bool, Option<String>, Option<f64>),
+);
+type PyCatBinState = (
+    usize,
+    Vec<i32>,
+    i32,
+    f64,
+    i32,
+    i32,
+    f64,
+    f64,
+    f64,
+    bool,
+    bool,
+    Option<f64>,
+);
+pub(crate) type BinningArgs = (usize, f64, f64);
+pub(crate) type BinningState = (String, bool);
+
+/// Accepts a policy name or a number, the latter meaning a fixed WoE.
+fn extract_unseen_policy(obj: &Bound<'_, PyAny>) -> PyResult<UnseenPolicy> {
+    if let Ok(woe) = obj.extract::<f64>() {
+        Ok(UnseenPolicy::FixedWoe(woe))
+    } else {
+        obj.extract::<String>()?.parse().map_err(to_pyerr)
+    }
+}
+
+/// Accepts either a list of codes (one bin per code) or a dict mapping a
+/// group name to the list of codes sharing its bin.
+fn extract_special_codes(obj: &Bound<'_, PyAny>) -> PyResult<Vec<SpecialGroup>> {
+    if let Ok(groups) = obj.cast::<PyDict>() {
+        groups
+            .iter()
+            .map(|(name, values)| {
+                Ok(SpecialGroup::new(
+                    name.extract::<String>()?,
+                    values.extract()?,
+                ))
+            })
+            .collect()
+    } else {
+        Ok(SpecialGroup::from_values(&obj.extract::<Vec<f64>>()?))
+    }
+}
+
+#[pyclass(module = "fastbinning", skip_from_py_object)]
 #[derive(Clone)]
 pub struct PyNumBin {
     #[pyo3(get)]
@@ -27,9 +107,135 @@ pub struct PyNumBin {
     pub event_rate: f64,
     #[pyo3(get)]
     pub is_missing: bool,
+    #[pyo3(get)]
+    pub is_special: bool,
+    #[pyo3(get)]
+    pub special_name: Option<String>,
+    #[pyo3(get)]
+    pub pvalue: Option<f64>,
+}
+
+#[pymethods]
+impl PyNumBin {
+    #[new]
+    #[pyo3(signature = (
+        bin_id, range, count, bin_pct, pos, neg, woe, iv, event_rate, is_missing,
+        is_special=false, special_name=None, pvalue=None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn pynew(
+        bin_id: usize,
+        range: (f64, f64),
+        count: i32,
+        bin_pct: f64,
+        pos: i32,
+        neg: i32,
+        woe: f64,
+        iv: f64,
+        event_rate: f64,
+        is_missing: bool,
+        is_special: bool,
+        special_name: Option<String>,
+        pvalue: Option<f64>,
+    ) -> Self {
+        Self {
+            bin_id,
+            range,
+            count,
+            bin_pct,
+            pos,
+            neg,
+            woe,
+            iv,
+            event_rate,
+            is_missing,
+            is_special,
+            special_name,
+            pvalue,
+        }
+    }
+
+    pub fn __getstate__(&self) -> PyNumBinState {
+        (
+            self.bin_id,
+            self.range,
+            self.count,
+            self.bin_pct,
+            self.pos,
+            self.neg,
+            self.woe,
+            self.iv,
+            self.event_rate,
+            self.is_missing,
+            (self.is_special, self.special_name.clone(), self.pvalue),
+        )
+    }
+
+    pub fn __setstate__(&mut self, state: PyNumBinState) {
+        (
+            self.bin_id,
+            self.range,
+            self.count,
+            self.bin_pct,
+            self.pos,
+            self.neg,
+            self.woe,
+            self.iv,
+            self.event_rate,
+            self.is_missing,
+            (self.is_special, self.special_name, self.pvalue),
+        ) = state;
+    }
+
+    /// The optional fields are restored by `__setstate__`, as the state has
+    /// more fields than fit in one argument tuple.
+    pub fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> (Bound<'py, PyType>, PyNumBinArgs, PyNumBinState) {
+        let this = slf.borrow();
+        let args = (
+            this.bin_id,
+            this.range,
+            this.count,
+            this.bin_pct,
+            this.pos,
+            this.neg,
+            this.woe,
+            this.iv,
+            this.event_rate,
+            this.is_missing,
+        );
+        (slf.get_type(), args, this.__getstate__())
+    }
+}
+
+impl PyNumBin {
+    pub(crate) fn from_bin(b: &NumBin, total_weight: f64) -> Self {
+        let count = b.count();
+        let bin_pct = if total_weight > 0.0 {
+            b.total_weight() / total_weight
+        } else {
+            0.0
+        };
+        PyNumBin {
+            bin_id: b.bin_id,
+            range: b.range,
+            count,
+            bin_pct,
+            pos: b.pos,
+            neg: b.neg,
+            woe: b.woe,
+            iv: b.iv,
+            event_rate: b.event_rate(),
+            is_missing: b.is_missing,
+            is_special: b.is_special(),
+            special_name: b.special.as_ref().map(|g| g.name.clone()),
+            pvalue: b.pvalue,
+        }
+    }
 }
 
-#[pyclass(skip_from_py_object)]
+#[pyclass(module = "fastbinning", skip_from_py_object)]
 #[derive(Clone)]
 pub struct PyCatBin {
     #[pyo3(get)]
@@ -52,43 +258,232 @@ pub struct PyCatBin {
     pub event_rate: f64,
     #[pyo3(get)]
     pub is_missing: bool,
+    #[pyo3(get)]
+    pub is_special: bool,
+    #[pyo3(get)]
+    pub pvalue: Option<f64>,
 }
 
-#[pyclass]
+#[pymethods]
+impl PyCatBin {
+    #[new]
+    #[pyo3(signature = (
+        bin_id, indices, count, bin_pct, pos, neg, woe, iv, event_rate, is_missing,
+        is_special=false, pvalue=None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn pynew(
+        bin_id: usize,
+        indices: Vec<i32>,
+        count: i32,
+        bin_pct: f64,
+        pos: i32,
+        neg: i32,
+        woe: f64,
+        iv: f64,
+        event_rate: f64,
+        is_missing: bool,
+        is_special: bool,
+        pvalue: Option<f64>,
+    ) -> Self {
+        Self {
+            bin_id,
+            indices,
+            count,
+            bin_pct,
+            pos,
+            neg,
+            woe,
+            iv,
+            event_rate,
+            is_missing,
+            is_special,
+            pvalue,
+        }
+    }
+
+    pub fn __getstate__(&self) -> PyCatBinState {
+        (
+            self.bin_id,
+            self.indices.clone(),
+            self.count,
+            self.bin_pct,
+            self.pos,
+            self.neg,
+            self.woe,
+            self.iv,
+            self.event_rate,
+            self.is_missing,
+            self.is_special,
+            self.pvalue,
+        )
+    }
+
+    pub fn __setstate__(&mut self, state: PyCatBinState) {
+        (
+            self.bin_id,
+            self.indices,
+            self.count,
+            self.bin_pct,
+            self.pos,
+            self.neg,
+            self.woe,
+            self.iv,
+            self.event_rate,
+            self.is_missing,
+            self.is_special,
+            self.pvalue,
+        ) = state;
+    }
+
+    pub fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, PyCatBinState) {
+        (slf.get_type(), slf.borrow().__getstate__())
+    }
+}
+
+impl PyCatBin {
+    pub(crate) fn from_bin(b: &CatBin, total_weight: f64) -> Self {
+        let count = b.count();
+        let bin_pct = if total_weight > 0.0 {
+            b.total_weight() / total_weight
+        } else {
+            0.0
+        };
+        PyCatBin {
+            bin_id: b.bin_id,
+            indices: b.indices.clone(),
+            count,
+            bin_pct,
+            pos: b.pos,
+            neg: b.neg,
+            woe: b.woe,
+            iv: b.iv,
+            event_rate: b.event_rate(),
+            is_missing: b.is_missing,
+            is_special: b.is_special,
+            pvalue: b.pvalue,
+        }
+    }
+}
+
+#[pyclass(module = "fastbinning")]
 pub struct NumericalBinning {
-    pub max_bins: usize,
-    pub min_bin_pct: f64,
-    pub max_bin_pct: f64,
-    pub _bins: Option<Vec<NumBin>>,
+    pub inner: CoreNumericalBinning,
+    pub _model: Option<NumericalModel>,
 }
 
 #[pymethods]
 impl NumericalBinning {
     #[new]
-    pub fn pynew(max_bins: usize, min_bin_pct: f64, max_bin_pct: f64) -> PyResult<Self> {
-        if min_bin_pct >= max_bin_pct {
-            return Err(PyValueError::new_err(format!(
-                "Invalid constraints: min_bin_pct ({}) must be less than max_bin_pct ({})",
-                min_bin_pct, max_bin_pct
-            )));
-        }
-        if min_bin_pct < 0.0 || max_bin_pct > 1.0 {
-            return Err(PyValueError::new_err(
-                "Bin percentages must be in the range [0.0, 1.0]",
-            ));
+    #[pyo3(signature = (
+        max_bins, min_bin_pct, max_bin_pct, special_codes=None, monotonic_trend="auto_asc_desc",
+        penalty="log_barrier", penalty_strength=5.0, target_bin_pct=None, solver="fast",
+        divergence="iv", prebinning="quantile", n_prebins=None, user_splits=None,
+        locked_splits=None, min_bin_n_event=None, min_bin_n_nonevent=None, max_bin_n_event=None,
+        max_pvalue=None, pvalue_test="chi2", min_event_rate_diff=None, min_woe_diff=None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn pynew(
+        max_bins: usize,
+        min_bin_pct: f64,
+        max_bin_pct: f64,
+        special_codes: Option<Bound<'_, PyAny>>,
+        monotonic_trend: &str,
+        penalty: &str,
+        penalty_strength: f64,
+        target_bin_pct: Option<f64>,
+        solver: &str,
+        divergence: &str,
+        prebinning: &str,
+        n_prebins: Option<usize>,
+        user_splits: Option<Vec<f64>>,
+        locked_splits: Option<Vec<f64>>,
+        min_bin_n_event: Option<i32>,
+        min_bin_n_nonevent: Option<i32>,
+        max_bin_n_event: Option<i32>,
+        max_pvalue: Option<f64>,
+        pvalue_test: &str,
+        min_event_rate_diff: Option<f64>,
+        min_woe_diff: Option<f64>,
+    ) -> PyResult<Self> {
+        let pvalue_test: PValueTest = pvalue_test.parse().map_err(to_pyerr)?;
+        let user_splits = match (user_splits, locked_splits) {
+            (Some(_), Some(_)) => {
+                return Err(PyValueError::new_err(
+                    "Pass either user_splits or locked_splits, not both",
+                ));
+            }
+            (Some(splits), None) => UserSplits::Fixed(splits),
+            (None, Some(splits)) => UserSplits::Locked(splits),
+            (None, None) => UserSplits::None,
+        };
+        let monotonic_trend: MonotonicTrend = monotonic_trend.parse().map_err(to_pyerr)?;
+        let solver: Solver = solver.parse().map_err(to_pyerr)?;
+        let divergence: Divergence = divergence.parse().map_err(to_pyerr)?;
+        let regularization = Regularization {
+            family: penalty.parse().map_err(to_pyerr)?,
+            strength: penalty_strength,
+            target_bin_pct,
+        };
+        let prebinning = Prebinning {
+            method: prebinning.parse().map_err(to_pyerr)?,
+            n_bins: n_prebins,
+        };
+        let mut config = NumericalConfig::new(max_bins, min_bin_pct, max_bin_pct)
+            .and_then(|c| c.with_regularization(regularization))
+            .and_then(|c| c.with_prebinning(prebinning))
+            .and_then(|c| c.with_user_splits(user_splits))
+            .and_then(|c| {
+                c.with_event_limits(EventLimits {
+                    min_bin_n_event,
+                    min_bin_n_nonevent,
+                    max_bin_n_event,
+                })
+            })
+            .and_then(|c| match max_pvalue {
+                Some(max_pvalue) => c.with_significance(Significance {
+                    max_pvalue,
+                    test: pvalue_test,
+                }),
+                None => Ok(c),
+            })
+            .and_then(|c| {
+                c.with_separation(Separation {
+                    min_event_rate_diff,
+                    min_woe_diff,
+                })
+            })
+            .map_err(to_pyerr)?
+            .with_monotonic_trend(monotonic_trend)
+            .with_solver(solver)
+            .with_divergence(divergence);
+        if let Some(codes) = special_codes {
+            let groups = extract_special_codes(&codes)?;
+            config = config.with_special_codes(groups).map_err(to_pyerr)?;
         }
-        Ok(Self::new(max_bins, min_bin_pct, max_bin_pct))
+        Ok(Self {
+            inner: CoreNumericalBinning::new(config),
+            _model: None,
+        })
     }
 
+    #[pyo3(signature = (x, y, sample_weight=None))]
     pub fn fit(
         &mut self,
+        py: Python<'_>,
         x: PyReadonlyArray1<f64>,
         y: PyReadonlyArray1<i32>,
+        sample_weight: Option<PyReadonlyArray1<f64>>,
     ) -> PyResult<Vec<PyNumBin>> {
-        let x_view = x.as_array();
-        let y_view = y.as_array();
-        let results = self.execute_fit(x_view, y_view);
-        self._bins = Some(results);
+        let x_owned = x.as_array().to_owned();
+        let y_owned = y.as_array().to_owned();
+        let w_owned = sample_weight.map(|w| w.as_array().to_owned());
+        let inner = &self.inner;
+        let model = py.detach(|| match &w_owned {
+            Some(w) => inner.fit_weighted(x_owned.view(), y_owned.view(), w.view()),
+            None => inner.fit(x_owned.view(), y_owned.view()),
+        });
+        self._model = Some(model.map_err(to_pyerr)?);
         self.bins()
     }
 
@@ -97,100 +492,278 @@ impl NumericalBinning {
         py: Python<'py>,
         x: PyReadonlyArray1<'py, f64>,
     ) -> PyResult<Bound<'py, PyArray1<f64>>> {
-        let _bins = self._bins.as_ref().ok_or(PyRuntimeError::new_err(
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
             "NotFittedError: Call fit() before transform()",
         ))?;
-        let x_view = x.as_array();
-        let output: Vec<f64> = self.execute_transform(x_view, _bins);
+        let x_owned = x.as_array().to_owned();
+        let output: Vec<f64> = py.detach(|| model.transform(x_owned.view()));
         Ok(output.into_pyarray(py))
     }
 
+    #[pyo3(signature = (x_new, y_new=None))]
+    pub fn stability(
+        &self,
+        py: Python<'_>,
+        x_new: PyReadonlyArray1<'_, f64>,
+        y_new: Option<PyReadonlyArray1<'_, i32>>,
+    ) -> PyResult<PyStability> {
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before stability()",
+        ))?;
+        let x_owned = x_new.as_array().to_owned();
+        let y_owned = y_new.map(|y| y.as_array().to_owned());
+        let stability = py
+            .detach(|| model.stability(x_owned.view(), y_owned.as_ref().map(|y| y.view())))
+            .map_err(to_pyerr)?;
+        Ok(PyStability::from_stability(&stability))
+    }
+
+    pub fn evaluate(
+        &self,
+        py: Python<'_>,
+        x: PyReadonlyArray1<'_, f64>,
+        y: PyReadonlyArray1<'_, i32>,
+    ) -> PyResult<PyNumEvaluation> {
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before evaluate()",
+        ))?;
+        let x_owned = x.as_array().to_owned();
+        let y_owned = y.as_array().to_owned();
+        let evaluation = py
+            .detach(|| model.evaluate(x_owned.view(), y_owned.view()))
+            .map_err(to_pyerr)?;
+        Ok(PyNumEvaluation::from_evaluation(&evaluation))
+    }
+
+    #[pyo3(signature = (x, y, sample_weight=None))]
     pub fn fit_transform<'py>(
         &mut self,
         py: Python<'py>,
         x: PyReadonlyArray1<'py, f64>,
         y: PyReadonlyArray1<'py, i32>,
+        sample_weight: Option<PyReadonlyArray1<'py, f64>>,
     ) -> PyResult<Bound<'py, PyArray1<f64>>> {
-        self.fit(x.clone(), y)?;
+        self.fit(py, x.clone(), y, sample_weight)?;
         self.transform(py, x)
     }
 
+    pub fn to_json(&self) -> PyResult<String> {
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before to_json()",
+        ))?;
+        model.to_json().map_err(to_pyerr)
+    }
+
+    #[staticmethod]
+    pub fn from_json(json: &str) -> PyResult<Self> {
+        let model = NumericalModel::from_json(json).map_err(to_pyerr)?;
+        Ok(Self {
+            inner: CoreNumericalBinning::new(model.config.clone()),
+            _model: Some(model),
+        })
+    }
+
+    pub fn __getstate__(&self) -> PyResult<BinningState> {
+        let model = self._model.clone().unwrap_or_else(|| NumericalModel {
+            config: self.inner.config.clone(),
+            bins: Vec::new(),
+            trend: None,
+            score: None,
+            gap: None,
+            prebins: None,
+        });
+        let json = model.to_json().map_err(to_pyerr)?;
+        Ok((json, self._model.is_some()))
+    }
+
+    pub fn __setstate__(&mut self, state: BinningState) -> PyResult<()> {
+        let (json, is_fitted) = state;
+        let model = NumericalModel::from_json(&json).map_err(to_pyerr)?;
+        self.inner = CoreNumericalBinning::new(model.config.clone());
+        self._model = is_fitted.then_some(model);
+        Ok(())
+    }
+
+    pub fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> PyResult<(Bound<'py, PyType>, BinningArgs, BinningState)> {
+        let this = slf.borrow();
+        let config = &this.inner.config;
+        let args = (config.max_bins, config.min_bin_pct, config.max_bin_pct);
+        Ok((slf.get_type(), args, this.__getstate__()?))
+    }
+
+    /// IV of the optimized bins, or the selected `divergence` when it is not
+    /// IV, excluding missing and special bins.
+    #[getter]
+    pub fn raw_iv(&self) -> PyResult<Option<f64>> {
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before 'raw_iv'",
+        ))?;
+        Ok(model.score.map(|s| s.iv))
+    }
+
+    /// `raw_iv` minus the bin-size penalty, the objective the solver maximized.
+    #[getter]
+    pub fn penalized_score(&self) -> PyResult<Option<f64>> {
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before 'penalized_score'",
+        ))?;
+        Ok(model.score.map(|s| s.penalized))
+    }
+
+    /// Merges regular bin `bin_id` with the next one. Returns the constraints
+    /// the edited bins break.
+    pub fn merge_bins(&mut self, bin_id: usize) -> PyResult<Vec<String>> {
+        let model = self._model.as_mut().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before merge_bins()",
+        ))?;
+        let violations = model.merge_bins(bin_id).map_err(to_pyerr)?;
+        Ok(violations.iter().map(|v| v.to_string()).collect())
+    }
+
+    /// Splits regular bin `bin_id` at `value`, rounded down to the nearest
+    /// prebin boundary. Returns the constraints the edited bins break.
+    pub fn split_bin(&mut self, bin_id: usize, value: f64) -> PyResult<Vec<String>> {
+        let model = self._model.as_mut().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before split_bin()",
+        ))?;
+        let violations = model.split_bin(bin_id, value).map_err(to_pyerr)?;
+        Ok(violations.iter().map(|v| v.to_string()).collect())
+    }
+
+    #[getter]
+    pub fn violations(&self) -> PyResult<Vec<String>> {
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before 'violations'",
+        ))?;
+        Ok(model.violations().iter().map(|v| v.to_string()).collect())
+    }
+
+    /// Relative penalized-score gap between the fast solver and the exact
+    /// optimum; `None` unless fitted with `solver="exact"`.
+    #[getter]
+    pub fn optimality_gap(&self) -> PyResult<Option<f64>> {
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before 'optimality_gap'",
+        ))?;
+        Ok(model.gap.map(|g| g.relative()))
+    }
+
+    /// The trend the fitted bins follow: "ascending", "descending", "peak",
+    /// "valley" or "none".
+    #[getter]
+    pub fn trend(&self) -> PyResult<Option<&'static str>> {
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before 'trend'",
+        ))?;
+        Ok(model.trend.map(|t| t.as_str()))
+    }
+
     #[getter]
     pub fn bins(&self) -> PyResult<Vec<PyNumBin>> {
-        let bins = self._bins.as_ref().ok_or(PyRuntimeError::new_err(
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
             "NotFittedError: Call fit() before 'bins'",
         ))?;
-        let total_count: i32 = bins.iter().map(|b| b.pos + b.neg).sum();
-        let total_count_f = total_count as f64;
-
-        let py_results = bins
+        let total_weight: f64 = model.bins.iter().map(|b| b.total_weight()).sum();
+        let py_results = model
+            .bins
             .iter()
-            .map(|b| {
-                let count = b.pos + b.neg;
-                let event_rate = if count > 0 {
-                    b.pos as f64 / count as f64
-                } else {
-                    0.0
-                };
-                let bin_pct = if total_count > 0 {
-                    count as f64 / total_count_f
-                } else {
-                    0.0
-                };
-
-                PyNumBin {
-                    bin_id: b.bin_id,
-                    range: b.range,
-                    count,
-                    bin_pct,
-                    pos: b.pos,
-                    neg: b.neg,
-                    woe: b.woe,
-                    iv: b.iv,
-                    event_rate,
-                    is_missing: b.is_missing,
-                }
-            })
+            .map(|b| PyNumBin::from_bin(b, total_weight))
             .collect();
         Ok(py_results)
     }
 }
 
-#[pyclass]
+#[pyclass(module = "fastbinning")]
 pub struct CategoricalBinning {
-    pub max_bins: usize,
-    pub min_bin_pct: f64,
-    pub max_bin_pct: f64,
-    pub _bins: Option<Vec<CatBin>>,
+    pub inner: CoreCategoricalBinning,
+    pub _model: Option<CategoricalModel>,
+    /// Set by `transform`, which shares `&self` with concurrent calls.
+    pub _n_unseen: AtomicUsize,
 }
 
 #[pymethods]
 impl CategoricalBinning {
     #[new]
-    pub fn pynew(max_bins: usize, min_bin_pct: f64, max_bin_pct: f64) -> PyResult<Self> {
-        if min_bin_pct >= max_bin_pct {
-            return Err(PyValueError::new_err(format!(
-                "Invalid constraints: min_bin_pct ({}) must be less than max_bin_pct ({})",
-                min_bin_pct, max_bin_pct
-            )));
-        }
-        if min_bin_pct < 0.0 || max_bin_pct > 1.0 {
-            return Err(PyValueError::new_err(
-                "Bin percentages must be in the range [0.0, 1.0]",
-            ));
-        }
-        Ok(Self::new(max_bins, min_bin_pct, max_bin_pct))
+    #[pyo3(signature = (
+        max_bins, min_bin_pct, max_bin_pct, missing_code=-1, special_codes=None, unseen=None,
+        divergence="iv", min_bin_n_event=None, min_bin_n_nonevent=None, max_bin_n_event=None,
+        max_pvalue=None, pvalue_test="chi2", min_event_rate_diff=None, min_woe_diff=None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn pynew(
+        max_bins: usize,
+        min_bin_pct: f64,
+        max_bin_pct: f64,
+        missing_code: i32,
+        special_codes: Option<Vec<i32>>,
+        unseen: Option<Bound<'_, PyAny>>,
+        divergence: &str,
+        min_bin_n_event: Option<i32>,
+        min_bin_n_nonevent: Option<i32>,
+        max_bin_n_event: Option<i32>,
+        max_pvalue: Option<f64>,
+        pvalue_test: &str,
+        min_event_rate_diff: Option<f64>,
+        min_woe_diff: Option<f64>,
+    ) -> PyResult<Self> {
+        let pvalue_test: PValueTest = pvalue_test.parse().map_err(to_pyerr)?;
+        let divergence: Divergence = divergence.parse().map_err(to_pyerr)?;
+        let unseen = match unseen {
+            Some(policy) => extract_unseen_policy(&policy)?,
+            None => UnseenPolicy::default(),
+        };
+        let config = CategoricalConfig::new(max_bins, min_bin_pct, max_bin_pct)
+            .and_then(|c| c.with_missing_code(missing_code))
+            .and_then(|c| c.with_special_codes(special_codes.unwrap_or_default()))
+            .and_then(|c| c.with_unseen(unseen))
+            .and_then(|c| {
+                c.with_event_limits(EventLimits {
+                    min_bin_n_event,
+                    min_bin_n_nonevent,
+                    max_bin_n_event,
+                })
+            })
+            .and_then(|c| match max_pvalue {
+                Some(max_pvalue) => c.with_significance(Significance {
+                    max_pvalue,
+                    test: pvalue_test,
+                }),
+                None => Ok(c),
+            })
+            .and_then(|c| {
+                c.with_separation(Separation {
+                    min_event_rate_diff,
+                    min_woe_diff,
+                })
+            })
+            .map_err(to_pyerr)?
+            .with_divergence(divergence);
+        Ok(Self {
+            inner: CoreCategoricalBinning::new(config),
+            _model: None,
+            _n_unseen: AtomicUsize::new(0),
+        })
     }
 
+    #[pyo3(signature = (x, y, sample_weight=None))]
     pub fn fit(
         &mut self,
+        py: Python<'_>,
         x: PyReadonlyArray1<i32>,
         y: PyReadonlyArray1<i32>,
+        sample_weight: Option<PyReadonlyArray1<f64>>,
     ) -> PyResult<Vec<PyCatBin>> {
-        let x_slice = x.as_slice().expect("x array must be contiguous");
-        let y_slice = y.as_slice().expect("y array must be contiguous");
-        let results = self.execute_fit(x_slice, y_slice);
-        self._bins = Some(results);
+        let x_vec = x.as_array().to_vec();
+        let y_vec = y.as_array().to_vec();
+        let w_vec = sample_weight.map(|w| w.as_array().to_vec());
+        let inner = &self.inner;
+        let model = py.detach(|| match &w_vec {
+            Some(w) => inner.fit_weighted(&x_vec, &y_vec, w),
+            None => inner.fit(&x_vec, &y_vec),
+        });
+        self._model = Some(model.map_err(to_pyerr)?);
         self.bins()
     }
 
@@ -199,64 +772,156 @@ impl CategoricalBinning {
         py: Python<'py>,
         x: PyReadonlyArray1<'py, i32>,
     ) -> PyResult<Bound<'py, PyArray1<f64>>> {
-        let _bins = self._bins.as_ref().ok_or_else(|| {
-            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                "NotFittedError: Call fit() before transform()",
-            )
-        })?;
-        let x_view = x.as_slice().expect("x array must be contiguous");
-        let output: Vec<f64> = self.execute_transform(x_view, _bins);
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before transform()",
+        ))?;
+        let x_vec = x.as_array().to_vec();
+        let result = py.detach(|| model.transform_with_unseen(&x_vec));
+        let (output, n_unseen) = result.map_err(to_pyerr)?;
+        self._n_unseen.store(n_unseen, Ordering::Relaxed);
         Ok(output.into_pyarray(py))
     }
 
+    #[pyo3(signature = (x_new, y_new=None))]
+    pub fn stability(
+        &self,
+        py: Python<'_>,
+        x_new: PyReadonlyArray1<'_, i32>,
+        y_new: Option<PyReadonlyArray1<'_, i32>>,
+    ) -> PyResult<PyStability> {
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before stability()",
+        ))?;
+        let x_vec = x_new.as_array().to_vec();
+        let y_vec = y_new.map(|y| y.as_array().to_vec());
+        let stability = py
+            .detach(|| model.stability(&x_vec, y_vec.as_deref()))
+            .map_err(to_pyerr)?;
+        Ok(PyStability::from_stability(&stability))
+    }
+
+    pub fn evaluate(
+        &self,
+        py: Python<'_>,
+        x: PyReadonlyArray1<'_, i32>,
+        y: PyReadonlyArray1<'_, i32>,
+    ) -> PyResult<PyCatEvaluation> {
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before evaluate()",
+        ))?;
+        let x_vec = x.as_array().to_vec();
+        let y_vec = y.as_array().to_vec();
+        let evaluation = py
+            .detach(|| model.evaluate(&x_vec, &y_vec))
+            .map_err(to_pyerr)?;
+        Ok(PyCatEvaluation::from_evaluation(&evaluation))
+    }
+
+    /// Number of values unseen during fit in the last `transform` call.
+    #[getter]
+    pub fn n_unseen(&self) -> usize {
+        self._n_unseen.load(Ordering::Relaxed)
+    }
+
+    #[pyo3(signature = (x, y, sample_weight=None))]
     pub fn fit_transform<'py>(
         &mut self,
         py: Python<'py>,
         x: PyReadonlyArray1<'py, i32>,
         y: PyReadonlyArray1<'py, i32>,
+        sample_weight: Option<PyReadonlyArray1<'py, f64>>,
     ) -> PyResult<Bound<'py, PyArray1<f64>>> {
-        self.fit(x.clone(), y)?;
+        self.fit(py, x.clone(), y, sample_weight)?;
         self.transform(py, x)
     }
 
+    pub fn to_json(&self) -> PyResult<String> {
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before to_json()",
+        ))?;
+        model.to_json().map_err(to_pyerr)
+    }
+
+    #[staticmethod]
+    pub fn from_json(json: &str) -> PyResult<Self> {
+        let model = CategoricalModel::from_json(json).map_err(to_pyerr)?;
+        Ok(Self {
+            inner: CoreCategoricalBinning::new(model.config.clone()),
+            _model: Some(model),
+            _n_unseen: AtomicUsize::new(0),
+        })
+    }
+
+    pub fn __getstate__(&self) -> PyResult<BinningState> {
+        let model = self._model.clone().unwrap_or_else(|| CategoricalModel {
+            config: self.inner.config.clone(),
+            bins: Vec::new(),
+            trend: None,
+            prebins: None,
+        });
+        let json = model.to_json().map_err(to_pyerr)?;
+        Ok((json, self._model.is_some()))
+    }
+
+    pub fn __setstate__(&mut self, state: BinningState) -> PyResult<()> {
+        let (json, is_fitted) = state;
+        let model = CategoricalModel::from_json(&json).map_err(to_pyerr)?;
+        self.inner = CoreCategoricalBinning::new(model.config.clone());
+        self._model = is_fitted.then_some(model);
+        Ok(())
+    }
+
+    pub fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> PyResult<(Bound<'py, PyType>, BinningArgs, BinningState)> {
+        let this = slf.borrow();
+        let config = &this.inner.config;
+        let args = (config.max_bins, config.min_bin_pct, config.max_bin_pct);
+        Ok((slf.get_type(), args, this.__getstate__()?))
+    }
+
+    /// Merges regular bin `bin_id` with the next one. Returns the constraints
+    /// the edited bins break.
+    pub fn merge_bins(&mut self, bin_id: usize) -> PyResult<Vec<String>> {
+        let model = self._model.as_mut().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before merge_bins()",
+        ))?;
+        let violations = model.merge_bins(bin_id).map_err(to_pyerr)?;
+        Ok(violations.iter().map(|v| v.to_string()).collect())
+    }
+
+    /// Moves `category` into regular bin `to_bin`. Returns the constraints the
+    /// edited bins break.
+    pub fn move_category(&mut self, category: i32, to_bin: usize) -> PyResult<Vec<String>> {
+        let model = self._model.as_mut().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before move_category()",
+        ))?;
+        let violations = model.move_category(category, to_bin).map_err(to_pyerr)?;
+        Ok(violations.iter().map(|v| v.to_string()).collect())
+    }
+
+    #[getter]
+    pub fn violations(&self) -> PyResult<Vec<String>> {
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before 'violations'",
+        ))?;
+        Ok(model.violations().iter().map(|v| v.to_string()).collect())
+    }
+
     #[getter]
     pub fn bins(&self) -> PyResult<Vec<PyCatBin>> {
-        let bins = self._bins.as_ref().ok_or_else(|| {
-            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+        let bins = &self
+            ._model
+            .as_ref()
+            .ok_or(PyRuntimeError::new_err(
                 "NotFittedError: Call fit() before accessing 'bins'",
-            )
-        })?;
+            ))?
+            .bins;
 
-        let total_count: i32 = bins.iter().map(|b| b.pos + b.neg).sum();
-        let total_count_f = total_count as f64;
+        let total_weight: f64 = bins.iter().map(|b| b.total_weight()).sum();
         let py_results = bins
             .iter()
-            .map(|b| {
-                let count = b.pos + b.neg;
-                let event_rate = if count > 0 {
-                    b.pos as f64 / count as f64
-                } else {
-                    0.0
-                };
-                let bin_pct = if total_count > 0 {
-                    count as f64 / total_count_f
-                } else {
-                    0.0
-                };
-
-                PyCatBin {
-                    bin_id: b.bin_id,
-                    indices: b.indices.clone(),
-                    count,
-                    bin_pct,
-                    pos: b.pos,
-                    neg: b.neg,
-                    woe: b.woe,
-                    iv: b.iv,
-                    event_rate,
-                    is_missing: b.is_missing,
-                }
-            })
+            .map(|b| PyCatBin::from_bin(b, total_weight))
             .collect();
         Ok(py_results)
     }
@@ -264,9 +929,19 @@ impl CategoricalBinning {
 
 #[pymodule]
 fn fastbinning(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<BinningProcess>()?;
     m.add_class::<CategoricalBinning>()?;
+    m.add_class::<ContinuousNumericalBinning>()?;
+    m.add_class::<MulticlassNumericalBinning>()?;
     m.add_class::<NumericalBinning>()?;
     m.add_class::<PyCatBin>()?;
+    m.add_class::<PyContBin>()?;
+    m.add_class::<PyMultiBin>()?;
     m.add_class::<PyNumBin>()?;
+    m.add_class::<PyStability>()?;
+    m.add_class::<PyStabilityBin>()?;
+    m.add_class::<PyNumEvaluation>()?;
+    m.add_class::<PyCatEvaluation>()?;
+    m.add_class::<PyVariableSummary>()?;
     Ok(())
 }
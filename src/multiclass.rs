@@ -0,0 +1,261 @@
+use crate::{BinningArgs, BinningState, to_pyerr};
+use fastbinning_core::{
+    MultiBin, MulticlassConfig, MulticlassModel, Prebinning,
+    multiclass::MulticlassBinning as CoreMulticlassBinning,
+};
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray1};
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use pyo3::types::PyType;
+
+type PyMultiBinState = (
+    usize,
+    (f64, f64),
+    i32,
+    f64,
+    Vec<i32>,
+    Vec<f64>,
+    Vec<f64>,
+    f64,
+    bool,
+);
+
+#[pyclass(module = "fastbinning", skip_from_py_object)]
+#[derive(Clone)]
+pub struct PyMultiBin {
+    #[pyo3(get)]
+    pub bin_id: usize,
+    #[pyo3(get)]
+    pub range: (f64, f64),
+    #[pyo3(get)]
+    pub count: i32,
+    #[pyo3(get)]
+    pub bin_pct: f64,
+    #[pyo3(get)]
+    pub counts: Vec<i32>,
+    #[pyo3(get)]
+    pub event_rates: Vec<f64>,
+    #[pyo3(get)]
+    pub woe: Vec<f64>,
+    #[pyo3(get)]
+    pub iv: f64,
+    #[pyo3(get)]
+    pub is_missing: bool,
+}
+
+#[pymethods]
+impl PyMultiBin {
+    #[new]
+    #[allow(clippy::too_many_arguments)]
+    pub fn pynew(
+        bin_id: usize,
+        range: (f64, f64),
+        count: i32,
+        bin_pct: f64,
+        counts: Vec<i32>,
+        event_rates: Vec<f64>,
+        woe: Vec<f64>,
+        iv: f64,
+        is_missing: bool,
+    ) -> Self {
+        Self {
+            bin_id,
+            range,
+            count,
+            bin_pct,
+            counts,
+            event_rates,
+            woe,
+            iv,
+            is_missing,
+        }
+    }
+
+    pub fn __getstate__(&self) -> PyMultiBinState {
+        (
+            self.bin_id,
+            self.range,
+            self.count,
+            self.bin_pct,
+            self.counts.clone(),
+            self.event_rates.clone(),
+            self.woe.clone(),
+            self.iv,
+            self.is_missing,
+        )
+    }
+
+    pub fn __setstate__(&mut self, state: PyMultiBinState) {
+        (
+            self.bin_id,
+            self.range,
+            self.count,
+            self.bin_pct,
+            self.counts,
+            self.event_rates,
+            self.woe,
+            self.iv,
+            self.is_missing,
+        ) = state;
+    }
+
+    pub fn __reduce__<'py>(slf: &Bound<'py, Self>) -> (Bound<'py, PyType>, PyMultiBinState) {
+        (slf.get_type(), slf.borrow().__getstate__())
+    }
+}
+
+impl PyMultiBin {
+    fn from_bin(b: &MultiBin, total_count: i32) -> Self {
+        let count = b.count();
+        let bin_pct = if total_count > 0 {
+            count as f64 / total_count as f64
+        } else {
+            0.0
+        };
+        PyMultiBin {
+            bin_id: b.bin_id,
+            range: b.range,
+            count,
+            bin_pct,
+            counts: b.counts.clone(),
+            event_rates: b.event_rates(),
+            woe: b.woe.clone(),
+            iv: b.iv,
+            is_missing: b.is_missing,
+        }
+    }
+}
+
+#[pyclass(module = "fastbinning")]
+pub struct MulticlassNumericalBinning {
+    pub inner: CoreMulticlassBinning,
+    pub _model: Option<MulticlassModel>,
+}
+
+#[pymethods]
+impl MulticlassNumericalBinning {
+    #[new]
+    #[pyo3(signature = (max_bins, min_bin_pct, max_bin_pct, prebinning="quantile", n_prebins=None))]
+    pub fn pynew(
+        max_bins: usize,
+        min_bin_pct: f64,
+        max_bin_pct: f64,
+        prebinning: &str,
+        n_prebins: Option<usize>,
+    ) -> PyResult<Self> {
+        let prebinning = Prebinning {
+            method: prebinning.parse().map_err(to_pyerr)?,
+            n_bins: n_prebins,
+        };
+        let config = MulticlassConfig::new(max_bins, min_bin_pct, max_bin_pct)
+            .and_then(|c| c.with_prebinning(prebinning))
+            .map_err(to_pyerr)?;
+        Ok(Self {
+            inner: CoreMulticlassBinning::new(config),
+            _model: None,
+        })
+    }
+
+    pub fn fit(
+        &mut self,
+        py: Python<'_>,
+        x: PyReadonlyArray1<f64>,
+        y: PyReadonlyArray1<i32>,
+    ) -> PyResult<Vec<PyMultiBin>> {
+        let x_owned = x.as_array().to_owned();
+        let y_owned = y.as_array().to_owned();
+        let inner = &self.inner;
+        let model = py.detach(|| inner.fit(x_owned.view(), y_owned.view()));
+        self._model = Some(model.map_err(to_pyerr)?);
+        self.bins()
+    }
+
+    /// Returns one one-vs-rest WoE column per class, in the order of `classes`.
+    pub fn transform<'py>(
+        &self,
+        py: Python<'py>,
+        x: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<Bound<'py, PyArray2<f64>>> {
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before transform()",
+        ))?;
+        let x_owned = x.as_array().to_owned();
+        let output = py.detach(|| model.transform(x_owned.view()));
+        Ok(output.into_pyarray(py))
+    }
+
+    pub fn fit_transform<'py>(
+        &mut self,
+        py: Python<'py>,
+        x: PyReadonlyArray1<'py, f64>,
+        y: PyReadonlyArray1<'py, i32>,
+    ) -> PyResult<Bound<'py, PyArray2<f64>>> {
+        self.fit(py, x.clone(), y)?;
+        self.transform(py, x)
+    }
+
+    pub fn to_json(&self) -> PyResult<String> {
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before to_json()",
+        ))?;
+        model.to_json().map_err(to_pyerr)
+    }
+
+    #[staticmethod]
+    pub fn from_json(json: &str) -> PyResult<Self> {
+        let model = MulticlassModel::from_json(json).map_err(to_pyerr)?;
+        Ok(Self {
+            inner: CoreMulticlassBinning::new(model.config.clone()),
+            _model: Some(model),
+        })
+    }
+
+    pub fn __getstate__(&self) -> PyResult<BinningState> {
+        let model = self._model.clone().unwrap_or_else(|| MulticlassModel {
+            config: self.inner.config.clone(),
+            classes: Vec::new(),
+            bins: Vec::new(),
+        });
+        let json = model.to_json().map_err(to_pyerr)?;
+        Ok((json, self._model.is_some()))
+    }
+
+    pub fn __setstate__(&mut self, state: BinningState) -> PyResult<()> {
+        let (json, is_fitted) = state;
+        let model = MulticlassModel::from_json(&json).map_err(to_pyerr)?;
+        self.inner = CoreMulticlassBinning::new(model.config.clone());
+        self._model = is_fitted.then_some(model);
+        Ok(())
+    }
+
+    pub fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> PyResult<(Bound<'py, PyType>, BinningArgs, BinningState)> {
+        let this = slf.borrow();
+        let config = &this.inner.config;
+        let args = (config.max_bins, config.min_bin_pct, config.max_bin_pct);
+        Ok((slf.get_type(), args, this.__getstate__()?))
+    }
+
+    #[getter]
+    pub fn classes(&self) -> PyResult<Vec<i32>> {
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before 'classes'",
+        ))?;
+        Ok(model.classes.clone())
+    }
+
+    #[getter]
+    pub fn bins(&self) -> PyResult<Vec<PyMultiBin>> {
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before 'bins'",
+        ))?;
+        let total_count: i32 = model.bins.iter().map(|b| b.count()).sum();
+        let py_results = model
+            .bins
+            .iter()
+            .map(|b| PyMultiBin::from_bin(b, total_count))
+            .collect();
+        Ok(py_results)
+    }
+}
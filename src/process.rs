@@ -0,0 +1,192 @@
+use crate::{BinningState, CategoricalBinning, NumericalBinning, to_pyerr};
+use fastbinning_core::{
+    BinningProcessModel, CategoricalModel, FittedVariable, NumericalModel, VariableConfig,
+    VariableSummary, process::BinningProcess as CoreBinningProcess,
+};
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray1, PyReadonlyArray2};
+use pyo3::exceptions::{PyRuntimeError, PyTypeError};
+use pyo3::prelude::*;
+use pyo3::types::PyType;
+
+type ProcessArgs = (Vec<Py<PyAny>>,);
+
+#[pyclass(module = "fastbinning", skip_from_py_object)]
+#[derive(Clone)]
+pub struct PyVariableSummary {
+    #[pyo3(get)]
+    pub index: usize,
+    #[pyo3(get)]
+    pub kind: String,
+    #[pyo3(get)]
+    pub n_bins: usize,
+    #[pyo3(get)]
+    pub iv: f64,
+}
+
+impl PyVariableSummary {
+    fn from_summary(s: &VariableSummary) -> Self {
+        PyVariableSummary {
+            index: s.index,
+            kind: s.kind.as_str().to_string(),
+            n_bins: s.n_bins,
+            iv: s.iv,
+        }
+    }
+}
+
+#[pyclass(module = "fastbinning")]
+pub struct BinningProcess {
+    pub inner: CoreBinningProcess,
+    pub _model: Option<BinningProcessModel>,
+}
+
+#[pymethods]
+impl BinningProcess {
+    #[new]
+    pub fn pynew(binnings: Vec<Bound<'_, PyAny>>) -> PyResult<Self> {
+        let configs = binnings
+            .iter()
+            .map(|b| {
+                if let Ok(num) = b.cast::<NumericalBinning>() {
+                    Ok(VariableConfig::Numerical(num.borrow().inner.config.clone()))
+                } else if let Ok(cat) = b.cast::<CategoricalBinning>() {
+                    Ok(VariableConfig::Categorical(
+                        cat.borrow().inner.config.clone(),
+                    ))
+                } else {
+                    Err(PyTypeError::new_err(
+                        "binnings must contain NumericalBinning or CategoricalBinning instances",
+                    ))
+                }
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(Self {
+            inner: CoreBinningProcess::new(configs),
+            _model: None,
+        })
+    }
+
+    #[pyo3(signature = (x, y, sample_weight=None))]
+    pub fn fit(
+        &mut self,
+        py: Python<'_>,
+        x: PyReadonlyArray2<f64>,
+        y: PyReadonlyArray1<i32>,
+        sample_weight: Option<PyReadonlyArray1<f64>>,
+    ) -> PyResult<Vec<PyVariableSummary>> {
+        let x_owned = x.as_array().to_owned();
+        let y_owned = y.as_array().to_owned();
+        let w_owned = sample_weight.map(|w| w.as_array().to_owned());
+        let inner = &self.inner;
+        let model = py
+            .detach(|| {
+                inner.fit(
+                    x_owned.view(),
+                    y_owned.view(),
+                    w_owned.as_ref().map(|w| w.view()),
+                )
+            })
+            .map_err(to_pyerr)?;
+        self._model = Some(model);
+        self.summary()
+    }
+
+    pub fn transform<'py>(
+        &self,
+        py: Python<'py>,
+        x: PyReadonlyArray2<'py, f64>,
+    ) -> PyResult<Bound<'py, PyArray2<f64>>> {
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before transform()",
+        ))?;
+        let x_owned = x.as_array().to_owned();
+        let output = py
+            .detach(|| model.transform(x_owned.view()))
+            .map_err(to_pyerr)?;
+        Ok(output.into_pyarray(py))
+    }
+
+    #[pyo3(signature = (x, y, sample_weight=None))]
+    pub fn fit_transform<'py>(
+        &mut self,
+        py: Python<'py>,
+        x: PyReadonlyArray2<'py, f64>,
+        y: PyReadonlyArray1<'py, i32>,
+        sample_weight: Option<PyReadonlyArray1<'py, f64>>,
+    ) -> PyResult<Bound<'py, PyArray2<f64>>> {
+        self.fit(py, x.clone(), y, sample_weight)?;
+        self.transform(py, x)
+    }
+
+    #[getter]
+    pub fn summary(&self) -> PyResult<Vec<PyVariableSummary>> {
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before 'summary'",
+        ))?;
+        Ok(model
+            .summary()
+            .iter()
+            .map(PyVariableSummary::from_summary)
+            .collect())
+    }
+
+    pub fn to_json(&self) -> PyResult<String> {
+        let model = self._model.as_ref().ok_or(PyRuntimeError::new_err(
+            "NotFittedError: Call fit() before to_json()",
+        ))?;
+        model.to_json().map_err(to_pyerr)
+    }
+
+    #[staticmethod]
+    pub fn from_json(json: &str) -> PyResult<Self> {
+        let model = BinningProcessModel::from_json(json).map_err(to_pyerr)?;
+        Ok(Self {
+            inner: CoreBinningProcess::new(model.variables.iter().map(|v| v.config()).collect()),
+            _model: Some(model),
+        })
+    }
+
+    pub fn __getstate__(&self) -> PyResult<BinningState> {
+        let model = self._model.clone().unwrap_or_else(|| BinningProcessModel {
+            variables: self.inner.configs.iter().map(unfitted).collect(),
+        });
+        let json = model.to_json().map_err(to_pyerr)?;
+        Ok((json, self._model.is_some()))
+    }
+
+    pub fn __setstate__(&mut self, state: BinningState) -> PyResult<()> {
+        let (json, is_fitted) = state;
+        let model = BinningProcessModel::from_json(&json).map_err(to_pyerr)?;
+        self.inner = CoreBinningProcess::new(model.variables.iter().map(|v| v.config()).collect());
+        self._model = is_fitted.then_some(model);
+        Ok(())
+    }
+
+    /// The variable configs are restored by `__setstate__`, so the process
+    /// is rebuilt from an empty list.
+    pub fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> PyResult<(Bound<'py, PyType>, ProcessArgs, BinningState)> {
+        Ok((slf.get_type(), (Vec::new(),), slf.borrow().__getstate__()?))
+    }
+}
+
+/// A model without bins, so unfitted processes pickle through the same JSON.
+fn unfitted(config: &VariableConfig) -> FittedVariable {
+    match config {
+        VariableConfig::Numerical(c) => FittedVariable::Numerical(NumericalModel {
+            config: c.clone(),
+            bins: Vec::new(),
+            trend: None,
+            score: None,
+            gap: None,
+            prebins: None,
+        }),
+        VariableConfig::Categorical(c) => FittedVariable::Categorical(CategoricalModel {
+            config: c.clone(),
+            bins: Vec::new(),
+            trend: None,
+            prebins: None,
+        }),
+    }
+}
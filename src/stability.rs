@@ -0,0 +1,62 @@
+use fastbinning_core::{Stability, StabilityBin};
+use pyo3::prelude::*;
+
+#[pyclass(module = "fastbinning", skip_from_py_object)]
+#[derive(Clone)]
+pub struct PyStabilityBin {
+    #[pyo3(get)]
+    pub bin_id: usize,
+    #[pyo3(get)]
+    pub expected_pct: f64,
+    #[pyo3(get)]
+    pub actual_pct: f64,
+    #[pyo3(get)]
+    pub psi: f64,
+    #[pyo3(get)]
+    pub event_rate: Option<f64>,
+    #[pyo3(get)]
+    pub woe: Option<f64>,
+    #[pyo3(get)]
+    pub iv: Option<f64>,
+    #[pyo3(get)]
+    pub woe_drift: Option<f64>,
+    #[pyo3(get)]
+    pub iv_drift: Option<f64>,
+}
+
+impl PyStabilityBin {
+    fn from_bin(b: &StabilityBin) -> Self {
+        PyStabilityBin {
+            bin_id: b.bin_id,
+            expected_pct: b.expected_pct,
+            actual_pct: b.actual_pct,
+            psi: b.psi,
+            event_rate: b.drift.map(|d| d.event_rate),
+            woe: b.drift.and_then(|d| d.woe),
+            iv: b.drift.and_then(|d| d.iv),
+            woe_drift: b.drift.and_then(|d| d.woe_drift),
+            iv_drift: b.drift.and_then(|d| d.iv_drift),
+        }
+    }
+}
+
+#[pyclass(module = "fastbinning", skip_from_py_object)]
+#[derive(Clone)]
+pub struct PyStability {
+    #[pyo3(get)]
+    pub psi: f64,
+    #[pyo3(get)]
+    pub n_unbinned: usize,
+    #[pyo3(get)]
+    pub bins: Vec<PyStabilityBin>,
+}
+
+impl PyStability {
+    pub(crate) fn from_stability(s: &Stability) -> Self {
+        PyStability {
+            psi: s.psi,
+            n_unbinned: s.n_unbinned,
+            bins: s.bins.iter().map(PyStabilityBin::from_bin).collect(),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+import threading
+import time
+import unittest
+
+from fastbinning import CategoricalBinning, NumericalBinning
+
+try:
+    import numpy as np
+except ImportError:  # pragma: no cover
+    np = None
+
+
+def ticks_during(call):
+    """Counts how often a Python thread runs while `call` executes.
+
+    A call holding the GIL blocks the ticker for its whole duration, so only
+    a call that releases it lets the count grow.
+    """
+    ticks = [0]
+    done = threading.Event()
+
+    def ticker():
+        while not done.is_set():
+            ticks[0] += 1
+            time.sleep(0.001)
+
+    thread = threading.Thread(target=ticker)
+    thread.start()
+    time.sleep(0.01)
+    try:
+        before = ticks[0]
+        start = time.perf_counter()
+        call()
+        elapsed = time.perf_counter() - start
+        during = ticks[0] - before
+    finally:
+        done.set()
+        thread.join()
+    return during, elapsed
+
+
+@unittest.skipUnless(np is not None, "requires numpy")
+class TestGilRelease(unittest.TestCase):
+    n = 2_000_000
+
+    def assert_releases_gil(self, call):
+        during, elapsed = ticks_during(call)
+        if elapsed < 0.05:
+            self.skipTest("call too fast to observe the ticker")
+        self.assertGreater(during, 5)
+
+    def test_numerical_fit_and_transform(self):
+        rng = np.random.default_rng(0)
+        x = rng.uniform(0, 100, self.n)
+        y = (rng.uniform(size=self.n) < 0.3 - 0.002 * x).astype(np.int32)
+        binning = NumericalBinning(5, 0.05, 0.5)
+        self.assert_releases_gil(lambda: binning.fit(x, y))
+        self.assert_releases_gil(lambda: binning.transform(x))
+
+    def test_categorical_fit_and_transform(self):
+        rng = np.random.default_rng(1)
+        x = rng.integers(0, 20, self.n).astype(np.int32)
+        y = (rng.uniform(size=self.n) < 0.05 + 0.01 * x).astype(np.int32)
+        binning = CategoricalBinning(5, 0.05, 0.5)
+        self.assert_releases_gil(lambda: binning.fit(x, y))
+        self.assert_releases_gil(lambda: binning.transform(x))
+
+
+if __name__ == "__main__":
+    unittest.main()
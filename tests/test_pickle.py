@@ -0,0 +1,76 @@
+import math
+import pickle
+import unittest
+
+from fastbinning import CategoricalBinning, NumericalBinning, PyCatBin, PyNumBin
+
+try:
+    import numpy as np
+except ImportError:  # pragma: no cover
+    np = None
+
+
+class TestBinPickle(unittest.TestCase):
+    def test_missing_numerical_bin_keeps_nan_range(self):
+        b = PyNumBin(3, (math.nan, math.nan), 10, 0.1, 2, 8, 0.3, 0.01, 0.2, True)
+        restored = pickle.loads(pickle.dumps(b))
+        self.assertTrue(all(math.isnan(v) for v in restored.range))
+        self.assertTrue(restored.is_missing)
+        self.assertEqual(
+            (restored.bin_id, restored.pos, restored.neg, restored.woe),
+            (3, 2, 8, 0.3),
+        )
+
+    def test_special_numerical_bin_keeps_optional_fields(self):
+        b = PyNumBin(
+            4, (-999.0, -999.0), 5, 0.05, 1, 4, -0.1, 0.002, 0.2, False,
+            is_special=True, special_name="no_hit", pvalue=0.03,
+        )
+        restored = pickle.loads(pickle.dumps(b))
+        self.assertTrue(restored.is_special)
+        self.assertEqual(restored.special_name, "no_hit")
+        self.assertEqual(restored.pvalue, 0.03)
+
+    def test_missing_categorical_bin_keeps_missing_index(self):
+        b = PyCatBin(2, [-1], 10, 0.1, 2, 8, 0.3, 0.01, 0.2, True)
+        restored = pickle.loads(pickle.dumps(b))
+        self.assertEqual(restored.indices, [-1])
+        self.assertTrue(restored.is_missing)
+
+
+class TestBinningPickle(unittest.TestCase):
+    def test_unfitted_binnings_round_trip(self):
+        for binning in (NumericalBinning(5, 0.05, 0.5), CategoricalBinning(4, 0.05, 0.6)):
+            restored = pickle.loads(pickle.dumps(binning))
+            with self.assertRaises(RuntimeError):
+                restored.bins
+
+    @unittest.skipUnless(np is not None, "requires numpy")
+    def test_fitted_numerical_binning_round_trips(self):
+        rng = np.random.default_rng(0)
+        x = rng.uniform(0, 100, 5000)
+        x[::25] = np.nan
+        y = (rng.uniform(size=5000) < 0.3 - 0.002 * np.nan_to_num(x)).astype(np.int32)
+        binning = NumericalBinning(5, 0.05, 0.5)
+        binning.fit(x, y)
+        restored = pickle.loads(pickle.dumps(binning))
+        missing = [b for b in restored.bins if b.is_missing]
+        self.assertEqual(len(missing), 1)
+        self.assertTrue(math.isnan(missing[0].range[0]))
+        np.testing.assert_array_equal(binning.transform(x), restored.transform(x))
+
+    @unittest.skipUnless(np is not None, "requires numpy")
+    def test_fitted_categorical_binning_round_trips(self):
+        rng = np.random.default_rng(1)
+        x = rng.integers(0, 8, 5000).astype(np.int32)
+        x[::40] = -1
+        y = (rng.uniform(size=5000) < 0.05 + 0.05 * np.maximum(x, 0)).astype(np.int32)
+        binning = CategoricalBinning(4, 0.05, 0.6)
+        binning.fit(x, y)
+        restored = pickle.loads(pickle.dumps(binning))
+        self.assertIn([-1], [b.indices for b in restored.bins if b.is_missing])
+        np.testing.assert_array_equal(binning.transform(x), restored.transform(x))
+
+
+if __name__ == "__main__":
+    unittest.main()
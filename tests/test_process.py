@@ -0,0 +1,65 @@
+import pickle
+import unittest
+
+from fastbinning import BinningProcess, CategoricalBinning, NumericalBinning
+
+try:
+    import numpy as np
+except ImportError:  # pragma: no cover
+    np = None
+
+
+def make_process():
+    return BinningProcess([NumericalBinning(5, 0.05, 0.5), CategoricalBinning(4, 0.05, 0.6)])
+
+
+def make_data(n=3000):
+    rng = np.random.default_rng(1)
+    score = rng.uniform(0, 100, n)
+    code = rng.integers(0, 8, n).astype(np.float64)
+    code[::40] = np.nan
+    p = 0.3 - 0.002 * score + 0.02 * np.nan_to_num(code)
+    y = (rng.uniform(size=n) < p).astype(np.int32)
+    return np.column_stack([score, code]), y
+
+
+class TestBinningProcess(unittest.TestCase):
+    def test_unfitted_process_round_trips(self):
+        restored = pickle.loads(pickle.dumps(make_process()))
+        with self.assertRaises(RuntimeError):
+            restored.summary
+        with self.assertRaises(RuntimeError):
+            restored.to_json()
+
+    @unittest.skipUnless(np is not None, "requires numpy")
+    def test_unfitted_process_keeps_its_variables(self):
+        x, y = make_data()
+        restored = pickle.loads(pickle.dumps(make_process()))
+        kinds = [s.kind for s in restored.fit(x, y)]
+        self.assertEqual(kinds, ["numerical", "categorical"])
+
+    @unittest.skipUnless(np is not None, "requires numpy")
+    def test_fitted_process_round_trips(self):
+        x, y = make_data()
+        process = make_process()
+        process.fit(x, y)
+        for restored in (
+            pickle.loads(pickle.dumps(process)),
+            BinningProcess.from_json(process.to_json()),
+        ):
+            self.assertEqual(
+                [(s.n_bins, s.iv) for s in restored.summary],
+                [(s.n_bins, s.iv) for s in process.summary],
+            )
+            np.testing.assert_array_equal(restored.transform(x), process.transform(x))
+
+    @unittest.skipUnless(np is not None, "requires numpy")
+    def test_non_integral_category_is_rejected(self):
+        x, y = make_data()
+        x[3, 1] = 2.5
+        with self.assertRaises(ValueError):
+            make_process().fit(x, y)
+
+
+if __name__ == "__main__":
+    unittest.main()